@@ -2,6 +2,7 @@ use std::{rc::Rc, time::Instant};
 
 use gleam_core::{
     build::{Built, Codegen, NullTelemetry, Options, ProjectCompiler, Telemetry},
+    config::PackageConfig,
     manifest::Manifest,
     paths::ProjectPaths,
     warning::WarningEmitterIO,
@@ -28,10 +29,21 @@ pub(crate) fn main_with_warnings(
     options: Options,
     manifest: Manifest,
     warnings: Rc<dyn WarningEmitterIO>,
+) -> Result<Built> {
+    let root_config = crate::config::root_config()?;
+    main_with_root_config(options, manifest, warnings, root_config)
+}
+
+/// Build the project using the given config for the root package rather than
+/// the one in gleam.toml.
+pub(crate) fn main_with_root_config(
+    options: Options,
+    manifest: Manifest,
+    warnings: Rc<dyn WarningEmitterIO>,
+    root_config: PackageConfig,
 ) -> Result<Built> {
     let paths = crate::find_project_paths()?;
     let perform_codegen = options.codegen;
-    let root_config = crate::config::root_config()?;
     let telemetry: &'static dyn Telemetry = if options.no_print_progress {
         &NullTelemetry
     } else {
@@ -36,7 +36,7 @@ pub fn command(options: CompilePackage) -> Result<()> {
                 .javascript_prelude
                 .ok_or_else(|| Error::JavaScriptPreludeRequired)?,
         },
-        Target::FSharp => TargetCodegenConfiguration::FSharp,
+        Target::FSharp => TargetCodegenConfiguration::FSharp { dotnet_build: true },
     };
 
     tracing::info!("Compiling package");
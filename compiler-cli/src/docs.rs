@@ -77,6 +77,7 @@ pub fn build(options: BuildOptions) -> Result<()> {
             warnings_as_errors: false,
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            no_dotnet: false,
         },
         crate::build::download_dependencies(cli::Reporter::new())?,
     )?;
@@ -172,6 +173,7 @@ impl PublishCommand {
                 mode: Mode::Prod,
                 target: None,
                 no_print_progress: false,
+                no_dotnet: false,
             },
             crate::build::download_dependencies(cli::Reporter::new())?,
         )?;
@@ -1,10 +1,21 @@
 use camino::Utf8PathBuf;
+use std::rc::Rc;
+
+use ecow::EcoString;
 use gleam_core::{
     analyse::TargetSupport,
     build::{Codegen, Compile, Mode, Options, Target},
+    config::PackageConfig,
+    error::Error,
+    fsharp::PRELUDE_PROJECT_NAME,
+    io::{CommandExecutor, Stdio},
+    manifest::Manifest,
+    paths::{ProjectPaths, ARTEFACT_DIRECTORY_NAME},
     Result,
 };
 
+use crate::fs::{ConsoleWarningEmitter, ProjectIO};
+
 #[cfg(target_os = "windows")]
 static ENTRYPOINT_FILENAME: &str = "entrypoint.ps1";
 #[cfg(not(target_os = "windows"))]
@@ -49,6 +60,7 @@ pub(crate) fn erlang_shipment() -> Result<()> {
             mode,
             target: Some(target),
             no_print_progress: false,
+            no_dotnet: false,
         },
         crate::build::download_dependencies(crate::cli::Reporter::new())?,
     )?;
@@ -100,6 +112,213 @@ the {ENTRYPOINT_FILENAME} script.
     Ok(())
 }
 
+/// Compile the project to F# and pack it into a NuGet package using
+/// `dotnet pack`. The package metadata is taken from gleam.toml.
+pub(crate) fn nuget() -> Result<()> {
+    let paths = crate::find_project_paths()?;
+    let target = Target::FSharp;
+    let mode = Mode::Prod;
+    let build = paths.build_directory_for_target(mode, target);
+    let out = paths.nuget_package_directory();
+
+    // Reset the directories to ensure we have a clean slate and no old code
+    crate::fs::delete_directory(&build)?;
+    crate::fs::delete_directory(&out)?;
+    crate::fs::mkdir(&out)?;
+
+    let manifest = crate::build::download_dependencies(crate::cli::Reporter::new())?;
+    let config = crate::config::root_config()?;
+    let dependencies = nuget_dependencies(&config, &manifest);
+
+    // Build project in production mode
+    let built = crate::build::main(
+        Options {
+            root_target_support: TargetSupport::Enforced,
+            warnings_as_errors: false,
+            codegen: Codegen::All,
+            compile: Compile::All,
+            mode,
+            target: Some(target),
+            no_print_progress: false,
+            no_dotnet: false,
+        },
+        manifest,
+    )?;
+
+    // The project references the prelude and dependency projects, which
+    // `dotnet pack` turns into NuGet dependencies, so they are packed too.
+    let name = &built.root_package.config.name;
+    for project in nuget_projects(&paths, name, &dependencies) {
+        let status = ProjectIO::new().exec(
+            "dotnet",
+            &[
+                "pack".into(),
+                project.to_string(),
+                "--configuration".into(),
+                "Release".into(),
+                "--output".into(),
+                out.to_string(),
+            ],
+            &[],
+            None,
+            Stdio::Inherit,
+        )?;
+        if status != 0 {
+            return Err(Error::ShellCommand {
+                program: "dotnet".into(),
+                err: None,
+            });
+        }
+    }
+
+    crate::cli::print_exported(name);
+
+    println!(
+        "
+Your NuGet package has been generated to {out}.
+
+It can be published to a NuGet feed with `dotnet nuget push`.
+",
+    );
+
+    Ok(())
+}
+
+/// The packages the root package depends on, directly or through other
+/// dependencies, excluding dev dependencies.
+fn nuget_dependencies(config: &PackageConfig, manifest: &Manifest) -> Vec<EcoString> {
+    let mut dependencies: Vec<EcoString> = config.dependencies.keys().cloned().collect();
+    let mut index = 0;
+    while let Some(name) = dependencies.get(index).cloned() {
+        let requirements = manifest
+            .packages
+            .iter()
+            .filter(|package| package.name == name)
+            .flat_map(|package| package.requirements.iter());
+        for requirement in requirements {
+            if !dependencies.contains(requirement) {
+                dependencies.push(requirement.clone());
+            }
+        }
+        index += 1;
+    }
+    dependencies.sort();
+    dependencies
+}
+
+/// The projects to pack into NuGet packages: the prelude, the dependencies
+/// and finally the root package.
+fn nuget_projects(
+    paths: &ProjectPaths,
+    name: &str,
+    dependencies: &[EcoString],
+) -> Vec<Utf8PathBuf> {
+    std::iter::once(PRELUDE_PROJECT_NAME)
+        .chain(dependencies.iter().map(EcoString::as_str))
+        .chain(std::iter::once(name))
+        .map(|package| {
+            paths
+                .build_directory_for_package(Mode::Prod, Target::FSharp, package)
+                .join(ARTEFACT_DIRECTORY_NAME)
+                .join(format!("{package}.fsproj"))
+        })
+        .collect()
+}
+
+/// Compile the project to F# as an executable and publish it as a single
+/// native binary using .NET Native AOT, which is then copied to
+/// `build/native`.
+pub(crate) fn dotnet_native(runtime: Option<String>) -> Result<()> {
+    let paths = crate::find_project_paths()?;
+    let target = Target::FSharp;
+    let mode = Mode::Prod;
+    let build = paths.build_directory_for_target(mode, target);
+    let out = paths.native_binary_directory();
+
+    // Reset the directories to ensure we have a clean slate and no old code
+    crate::fs::delete_directory(&build)?;
+    crate::fs::delete_directory(&out)?;
+    crate::fs::mkdir(&out)?;
+
+    let mut config = crate::config::root_config()?;
+    config.fsharp.publish_native();
+
+    // Build project in production mode
+    let built = crate::build::main_with_root_config(
+        Options {
+            root_target_support: TargetSupport::Enforced,
+            warnings_as_errors: false,
+            codegen: Codegen::All,
+            compile: Compile::All,
+            mode,
+            target: Some(target),
+            no_print_progress: false,
+            no_dotnet: false,
+        },
+        crate::build::download_dependencies(crate::cli::Reporter::new())?,
+        Rc::new(ConsoleWarningEmitter),
+        config,
+    )?;
+
+    let name = &built.root_package.config.name;
+    let package_directory = paths.build_directory_for_package(mode, target, name);
+    let project_directory = package_directory.join(ARTEFACT_DIRECTORY_NAME);
+    let publish_directory = package_directory.join("publish");
+
+    let mut args = vec![
+        "publish".into(),
+        format!("{name}.fsproj"),
+        "--configuration".into(),
+        "Release".into(),
+        "--output".into(),
+        publish_directory.to_string(),
+    ];
+    if let Some(runtime) = &runtime {
+        args.push("--runtime".into());
+        args.push(runtime.clone());
+    }
+
+    let status = ProjectIO::new().exec(
+        "dotnet",
+        &args,
+        &[],
+        Some(&project_directory),
+        Stdio::Inherit,
+    )?;
+    if status != 0 {
+        return Err(Error::ShellCommand {
+            program: "dotnet".into(),
+            err: None,
+        });
+    }
+
+    // Only the binary is copied, the publish directory also contains debug
+    // symbols
+    let windows = match &runtime {
+        Some(runtime) => runtime.starts_with("win"),
+        None => cfg!(target_os = "windows"),
+    };
+    let binary_name = if windows {
+        format!("{name}.exe")
+    } else {
+        name.to_string()
+    };
+    let binary = out.join(&binary_name);
+    crate::fs::copy(publish_directory.join(&binary_name), &binary)?;
+
+    crate::cli::print_exported(name);
+
+    println!(
+        "
+Your native binary has been generated to {out}.
+
+    {binary}
+",
+    );
+
+    Ok(())
+}
+
 pub fn hex_tarball() -> Result<()> {
     let paths = crate::find_project_paths()?;
     let mut config = crate::config::root_config()?;
@@ -137,6 +356,7 @@ pub fn package_interface(path: Utf8PathBuf) -> Result<()> {
             warnings_as_errors: false,
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            no_dotnet: false,
         },
         crate::build::download_dependencies(crate::cli::Reporter::new())?,
     )?;
@@ -146,3 +366,77 @@ pub fn package_interface(path: Utf8PathBuf) -> Result<()> {
     crate::fs::write_outputs_under(&[out], crate::find_project_paths()?.root())?;
     Ok(())
 }
+
+#[test]
+fn nuget_packs_referenced_projects() {
+    use gleam_core::manifest::{Base16Checksum, ManifestPackage, ManifestPackageSource};
+
+    let config: PackageConfig = toml::from_str(
+        r#"
+name = "wibble"
+
+[dependencies]
+wobble = ">= 1.0.0 and < 2.0.0"
+
+[dev-dependencies]
+gleeunit = ">= 1.0.0 and < 2.0.0"
+"#,
+    )
+    .unwrap();
+    let package = |name: &str, requirements: &[&str]| ManifestPackage {
+        name: name.into(),
+        version: hexpm::version::Version::new(1, 0, 0),
+        build_tools: vec!["gleam".into()],
+        otp_app: None,
+        requirements: requirements.iter().map(|r| (*r).into()).collect(),
+        source: ManifestPackageSource::Hex {
+            outer_checksum: Base16Checksum(vec![]),
+        },
+    };
+    let manifest = Manifest {
+        requirements: Default::default(),
+        packages: vec![
+            package("gleam_stdlib", &[]),
+            package("gleeunit", &["gleam_stdlib"]),
+            package("wobble", &["wubble"]),
+            package("wubble", &[]),
+        ],
+    };
+    let paths = ProjectPaths::new(Utf8PathBuf::from("/wibble"));
+
+    let dependencies = nuget_dependencies(&config, &manifest);
+    assert_eq!(
+        dependencies,
+        vec![EcoString::from("wobble"), "wubble".into()]
+    );
+
+    let projects = nuget_projects(&paths, "wibble", &dependencies);
+    let project_directory = |name: &str| {
+        paths
+            .build_directory_for_package(Mode::Prod, Target::FSharp, name)
+            .join(ARTEFACT_DIRECTORY_NAME)
+    };
+    assert_eq!(
+        projects.last(),
+        Some(&project_directory("wibble").join("wibble.fsproj"))
+    );
+
+    // Every project referenced by a packed project is packed too
+    let references = [
+        ("wibble", PRELUDE_PROJECT_NAME),
+        ("wibble", "wobble"),
+        ("wobble", PRELUDE_PROJECT_NAME),
+        ("wobble", "wubble"),
+    ];
+    for (package, reference) in references {
+        let path = gleam_core::codegen::package_project_path(reference);
+        let mut resolved = project_directory(package);
+        for component in Utf8PathBuf::from(path).components() {
+            match component.as_str() {
+                ".." => assert!(resolved.pop()),
+                component => resolved.push(component),
+            }
+        }
+        assert!(projects.contains(&resolved), "{resolved} is not packed");
+    }
+}
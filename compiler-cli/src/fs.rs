@@ -414,7 +414,10 @@ pub fn native_files(dir: &Utf8Path) -> Result<impl Iterator<Item = Utf8PathBuf>
         .map(|e| e.into_path())
         .filter(|path| {
             let extension = path.extension().unwrap_or_default();
-            matches!(extension, "erl" | "hrl" | "ex" | "js" | "mjs" | "ts")
+            matches!(
+                extension,
+                "erl" | "hrl" | "ex" | "js" | "mjs" | "ts" | "fs" | "fsx"
+            )
         }))
 }
 
@@ -439,6 +442,20 @@ pub fn erlang_files(dir: &Utf8Path) -> Result<impl Iterator<Item = Utf8PathBuf>
         }))
 }
 
+/// Recursively find all the F# source files within a directory.
+pub fn fsharp_files(dir: &Utf8Path) -> impl Iterator<Item = Utf8PathBuf> + '_ {
+    walkdir::WalkDir::new(dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .map(|e| Utf8PathBuf::from_path_buf(e.into_path()).expect("Non Utf-8 Path"))
+        .filter(|path| {
+            let extension = path.extension().unwrap_or_default();
+            extension == "fs" || extension == "fsx"
+        })
+}
+
 pub fn create_tar_archive(outputs: Vec<OutputFile>) -> Result<Vec<u8>, Error> {
     tracing::trace!("creating_tar_archive");
 
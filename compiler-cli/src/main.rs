@@ -131,6 +131,11 @@ enum Command {
         /// Don't print progress information
         #[clap(long)]
         no_print_progress: bool,
+
+        /// Only generate F# source and project files for the fsharp target,
+        /// without compiling them with `dotnet build`
+        #[clap(long)]
+        no_dotnet: bool,
     },
 
     /// Type check the project
@@ -284,6 +289,17 @@ pub enum ExportTarget {
     JavascriptPrelude,
     /// The TypeScript prelude module
     TypescriptPrelude,
+    /// The package compiled to F# and packed into a NuGet package, suitable
+    /// for publishing to NuGet
+    Nuget,
+    /// The package compiled to F# and published as a single native binary
+    /// using .NET Native AOT
+    DotnetNative {
+        /// The runtime identifier to compile for, e.g. linux-x64. Defaults to
+        /// the current platform
+        #[arg(long)]
+        runtime: Option<String>,
+    },
     /// Information on the modules, functions, and types in the project in JSON format
     PackageInterface {
         #[arg(long = "out", required = true)]
@@ -457,7 +473,8 @@ fn main() {
             target,
             warnings_as_errors,
             no_print_progress,
-        } => command_build(target, warnings_as_errors, no_print_progress),
+            no_dotnet,
+        } => command_build(target, warnings_as_errors, no_print_progress, no_dotnet),
 
         Command::Check { target } => command_check(target),
 
@@ -541,6 +558,8 @@ fn main() {
         Command::Export(ExportTarget::HexTarball) => export::hex_tarball(),
         Command::Export(ExportTarget::JavascriptPrelude) => export::javascript_prelude(),
         Command::Export(ExportTarget::TypescriptPrelude) => export::typescript_prelude(),
+        Command::Export(ExportTarget::Nuget) => export::nuget(),
+        Command::Export(ExportTarget::DotnetNative { runtime }) => export::dotnet_native(runtime),
         Command::Export(ExportTarget::PackageInterface { output }) => {
             export::package_interface(output)
         }
@@ -570,6 +589,7 @@ fn command_check(target: Option<Target>) -> Result<()> {
             mode: Mode::Dev,
             target,
             no_print_progress: false,
+            no_dotnet: false,
         },
         build::download_dependencies(cli::Reporter::new())?,
     )?;
@@ -580,6 +600,7 @@ fn command_build(
     target: Option<Target>,
     warnings_as_errors: bool,
     no_print_progress: bool,
+    no_dotnet: bool,
 ) -> Result<()> {
     let manifest = if no_print_progress {
         build::download_dependencies(NullTelemetry)?
@@ -595,6 +616,7 @@ fn command_build(
             mode: Mode::Dev,
             target,
             no_print_progress,
+            no_dotnet,
         },
         manifest,
     )?;
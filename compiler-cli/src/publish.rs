@@ -6,6 +6,7 @@ use gleam_core::{
     config::{PackageConfig, SpdxLicense},
     docs::DocContext,
     error::SmallVersion,
+    fsharp::PRELUDE_PROJECT_NAME,
     hex,
     paths::{self, ProjectPaths},
     requirement::Requirement,
@@ -265,6 +266,7 @@ fn do_build_hex_tarball(paths: &ProjectPaths, config: &mut PackageConfig) -> Res
             codegen: Codegen::All,
             compile: Compile::All,
             no_print_progress: false,
+            no_dotnet: false,
         },
         build::download_dependencies(cli::Reporter::new())?,
     )?;
@@ -326,7 +328,7 @@ fn do_build_hex_tarball(paths: &ProjectPaths, config: &mut PackageConfig) -> Res
     let generated_files = match target {
         Target::Erlang => generated_erlang_files(paths, &built.root_package)?,
         Target::JavaScript => vec![],
-        Target::FSharp => todo!("Add F# generated files"),
+        Target::FSharp => generated_fsharp_files(paths, &built.root_package)?,
     };
     let src_files = project_files()?;
     let contents_tar_gz = contents_tarball(&src_files, &generated_files)?;
@@ -506,6 +508,74 @@ fn generated_erlang_files(
     Ok(files)
 }
 
+/// The generated F# sources are placed in an `fsharp` directory of the
+/// tarball, laid out the same way as in the build directory:
+///
+/// ```text
+/// fsharp/gleam_prelude/_gleam_artefacts/gleam_prelude.fsproj
+/// fsharp/<package>/_gleam_artefacts/<package>.fsproj
+/// ```
+///
+/// The prelude project is included so the package's `.fsproj` can be built
+/// from the tarball alone. Its references to dependency projects point to
+/// sibling directories, so a .NET consumer unpacks the `fsharp` directory of
+/// each dependency's tarball next to it. Gleam projects depending on the
+/// package don't use these files as they compile the Gleam sources.
+fn generated_fsharp_files(
+    paths: &ProjectPaths,
+    package: &Package,
+) -> Result<Vec<(Utf8PathBuf, String)>> {
+    let mut files = vec![];
+
+    let build_fsharp = paths.build_directory_for_target(Mode::Prod, Target::FSharp);
+    let tar_fsharp = Utf8Path::new("fsharp");
+
+    // The shared prelude project referenced by the package project
+    let prelude = Utf8Path::new(PRELUDE_PROJECT_NAME).join(paths::ARTEFACT_DIRECTORY_NAME);
+    for name in [
+        format!("{PRELUDE_PROJECT_NAME}.fsproj"),
+        format!("{PRELUDE_PROJECT_NAME}.fs"),
+    ] {
+        let path = prelude.join(name);
+        files.push((tar_fsharp.join(&path), fs::read(build_fsharp.join(&path))?));
+    }
+
+    let artefacts =
+        Utf8Path::new(package.config.name.as_str()).join(paths::ARTEFACT_DIRECTORY_NAME);
+    let build = build_fsharp.join(&artefacts);
+    let tar_artefacts = tar_fsharp.join(&artefacts);
+
+    let project_file = format!("{}.fsproj", &package.config.name);
+    for name in [project_file.as_str(), "gleam.toml"] {
+        files.push((tar_artefacts.join(name), fs::read(build.join(name))?));
+    }
+
+    // F# modules. Test modules are generated into a separate `test` directory
+    // and are not part of the published project.
+    let src = build.join("src");
+    if src.is_dir() {
+        for file in fs::fsharp_files(&src) {
+            let relative = file
+                .strip_prefix(&build)
+                .expect("generated_files module path");
+            files.push((tar_artefacts.join(relative), fs::read(&file)?));
+        }
+    }
+
+    // External F# files copied from the project
+    let external = build.join("external");
+    if external.is_dir() {
+        for file in fs::fsharp_files(&external) {
+            let name = file
+                .file_name()
+                .expect("generated_files external file name");
+            files.push((tar_artefacts.join("external").join(name), fs::read(&file)?));
+        }
+    }
+
+    Ok(files)
+}
+
 fn add_to_tar<P, W>(tarball: &mut tar::Builder<W>, path: P, data: &[u8]) -> Result<()>
 where
     P: AsRef<Utf8Path>,
@@ -733,6 +803,52 @@ fn prevent_publish_git_dependency() {
     );
 }
 
+#[test]
+fn fsharp_tarball_files_include_prelude_project() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create a temp directory");
+    let root = Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf())
+        .expect("Path should be valid UTF-8");
+    let paths = ProjectPaths::new(root);
+    let build = paths.build_directory_for_target(Mode::Prod, Target::FSharp);
+    for file in [
+        "gleam_prelude/_gleam_artefacts/gleam_prelude.fsproj",
+        "gleam_prelude/_gleam_artefacts/gleam_prelude.fs",
+        "wibble/_gleam_artefacts/wibble.fsproj",
+        "wibble/_gleam_artefacts/gleam.toml",
+        "wibble/_gleam_artefacts/src/wibble.fs",
+        "wibble/_gleam_artefacts/test/wibble_test.fs",
+        "wibble/_gleam_artefacts/external/ffi.fs",
+    ] {
+        fs::write(&build.join(file), file).expect("Failed to write build file");
+    }
+
+    let package = Package {
+        config: PackageConfig {
+            name: "wibble".into(),
+            ..Default::default()
+        },
+        modules: vec![],
+    };
+    let files = generated_fsharp_files(&paths, &package)
+        .expect("Generated F# files should be read")
+        .into_iter()
+        .map(|(path, _)| path.to_string())
+        .sorted()
+        .collect_vec();
+
+    assert_eq!(
+        files,
+        vec![
+            "fsharp/gleam_prelude/_gleam_artefacts/gleam_prelude.fs",
+            "fsharp/gleam_prelude/_gleam_artefacts/gleam_prelude.fsproj",
+            "fsharp/wibble/_gleam_artefacts/external/ffi.fs",
+            "fsharp/wibble/_gleam_artefacts/gleam.toml",
+            "fsharp/wibble/_gleam_artefacts/src/wibble.fs",
+            "fsharp/wibble/_gleam_artefacts/wibble.fsproj",
+        ]
+    );
+}
+
 pub fn get_hostname() -> String {
     hostname::get()
         .expect("Looking up hostname")
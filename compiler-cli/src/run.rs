@@ -5,7 +5,7 @@ use ecow::EcoString;
 use gleam_core::{
     analyse::TargetSupport,
     build::{Built, Codegen, Compile, Mode, NullTelemetry, Options, Runtime, Target, Telemetry},
-    config::{DenoFlag, PackageConfig},
+    config::{DenoFlag, FSharpOutputType, PackageConfig},
     error::Error,
     io::{CommandExecutor, Stdio},
     paths::{ProjectPaths, ARTEFACT_DIRECTORY_NAME},
@@ -95,10 +95,19 @@ pub fn command(
             PackageKind::Dependency => TargetSupport::NotEnforced,
         },
         no_print_progress,
+        no_dotnet: false,
     };
 
     let built = crate::build::main(options, manifest)?;
 
+    // Test modules are never compiled as .NET entry points, so their main
+    // function is called differently.
+    let is_test_module = built
+        .root_package
+        .modules
+        .iter()
+        .any(|m| m.name == module && m.is_test());
+
     // A module can not be run if it does not exist or does not have a public main function.
     let main_function = get_or_suggest_main_function(built, &module, target)?;
 
@@ -134,7 +143,15 @@ pub fn command(
                 target: Target::FSharp,
                 invalid_runtime: r,
             }),
-            _ => run_fsharp(&paths, &main_function.package, &module, arguments, which),
+            _ => run_fsharp(
+                &paths,
+                &mod_config,
+                &main_function.package,
+                &module,
+                is_test_module,
+                arguments,
+                which,
+            ),
         },
     }?;
 
@@ -177,21 +194,27 @@ fn run_erlang(
 
 fn run_fsharp(
     paths: &ProjectPaths,
+    config: &PackageConfig,
     package: &str,
     module: &str,
+    is_test_module: bool,
     arguments: Vec<String>,
     which: Which,
 ) -> Result<i32, Error> {
     let build_dir = paths
         .build_directory_for_package(Mode::Dev, Target::FSharp, package)
         .join(ARTEFACT_DIRECTORY_NAME);
-    // Run the generated F# project via dotnet run
     let mut args = match which {
-        Which::Src => vec![
-            "run".to_string(),
-            "--project".to_string(),
-            build_dir.join(format!("{package}.fsproj")).to_string(),
-        ],
+        Which::Src => {
+            // Run the chosen module's main function via a small entrypoint project
+            let entry = write_fsharp_entrypoint(paths, config, package, module, is_test_module)?;
+            vec![
+                "run".to_string(),
+                "--project".to_string(),
+                entry.to_string(),
+                "--".to_string(),
+            ]
+        }
         Which::Test => vec![
             "test".to_string(),
             build_dir.join(format!("{package}_test.fsproj")).to_string(),
@@ -202,11 +225,73 @@ fn run_fsharp(
         args.push(arg);
     }
 
-    println!("args: {:?}", args);
-
     ProjectIO::new().exec("dotnet", &args, &[], None, Stdio::Inherit)
 }
 
+/// Writes an executable project which references the package's project, or
+/// its test project for a test module, and calls the main function of the
+/// given module. Returns the path to the project file.
+fn write_fsharp_entrypoint(
+    paths: &ProjectPaths,
+    config: &PackageConfig,
+    package: &str,
+    module: &str,
+    is_test_module: bool,
+) -> Result<Utf8PathBuf, Error> {
+    let directory = paths
+        .build_directory_for_package(Mode::Dev, Target::FSharp, package)
+        .join("gleam.main");
+    let module = gleam_core::fsharp::sanitize_module_name(module);
+
+    // When the package is an executable the main functions of its src
+    // modules are compiled as .NET entry points, which take the command line
+    // arguments and return an exit code.
+    let main_is_entry_point = config.fsharp.output_type == FSharpOutputType::Exe && !is_test_module;
+    let call = if main_is_entry_point {
+        format!("{module}.main argv")
+    } else {
+        format!(
+            "{module}.main () |> ignore
+    0"
+        )
+    };
+    let source = format!(
+        r#"module Gleam.Main
+
+[<EntryPoint>]
+let main argv =
+    {call}
+"#,
+    );
+    crate::fs::write(&directory.join("gleam.main.fs"), &source)?;
+
+    // Test modules are only compiled into the test project
+    let referenced_project = if is_test_module {
+        format!("{package}_test.fsproj")
+    } else {
+        format!("{package}.fsproj")
+    };
+    let project = format!(
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <OutputType>Exe</OutputType>
+    <TargetFramework>{target_framework}</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>
+    <Compile Include="gleam.main.fs" />
+  </ItemGroup>
+  <ItemGroup>
+    <ProjectReference Include="../{ARTEFACT_DIRECTORY_NAME}/{referenced_project}" />
+  </ItemGroup>
+</Project>
+"#,
+        target_framework = config.fsharp.target_framework,
+    );
+    let path = directory.join("gleam.main.fsproj");
+    crate::fs::write(&path, &project)?;
+    Ok(path)
+}
+
 fn run_javascript_bun(
     paths: &ProjectPaths,
     package: &str,
@@ -414,3 +499,55 @@ fn valid_module_names() {
         assert!(is_gleam_module(mod_name));
     }
 }
+
+#[test]
+fn fsharp_entrypoint_calls_main() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).expect("Non Utf8 Path");
+    let paths = ProjectPaths::new(root);
+    let config = PackageConfig::default();
+
+    let project =
+        write_fsharp_entrypoint(&paths, &config, "wibble", "wibble/wobble", false).unwrap();
+
+    let directory = paths
+        .build_directory_for_package(Mode::Dev, Target::FSharp, "wibble")
+        .join("gleam.main");
+    assert_eq!(project, directory.join("gleam.main.fsproj"));
+    insta::assert_snapshot!(crate::fs::read(directory.join("gleam.main.fs")).unwrap());
+
+    let project = crate::fs::read(&project).unwrap();
+    assert!(project.contains(r#"<ProjectReference Include="../_gleam_artefacts/wibble.fsproj" />"#));
+    insta::assert_snapshot!(project);
+}
+
+#[test]
+fn fsharp_entrypoint_passes_arguments_to_entry_point() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).expect("Non Utf8 Path");
+    let paths = ProjectPaths::new(root);
+    let mut config = PackageConfig::default();
+    config.fsharp.output_type = FSharpOutputType::Exe;
+
+    let project = write_fsharp_entrypoint(&paths, &config, "wibble", "wibble", false).unwrap();
+
+    let source = crate::fs::read(project.with_file_name("gleam.main.fs")).unwrap();
+    insta::assert_snapshot!(source);
+}
+
+#[test]
+fn fsharp_entrypoint_references_test_project_for_test_module() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).expect("Non Utf8 Path");
+    let paths = ProjectPaths::new(root);
+    let mut config = PackageConfig::default();
+    config.fsharp.output_type = FSharpOutputType::Exe;
+
+    let project = write_fsharp_entrypoint(&paths, &config, "wibble", "wibble_test", true).unwrap();
+
+    let source = crate::fs::read(project.with_file_name("gleam.main.fs")).unwrap();
+    assert!(source.contains("wibble_test.main () |> ignore"));
+    let project = crate::fs::read(&project).unwrap();
+    assert!(project
+        .contains(r#"<ProjectReference Include="../_gleam_artefacts/wibble_test.fsproj" />"#));
+}
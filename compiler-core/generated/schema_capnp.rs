@@ -1435,6 +1435,14 @@ pub mod type_constructor {
     pub fn has_documentation(&self) -> bool {
       !self.reader.get_pointer_field(6).is_null()
     }
+    #[inline]
+    pub fn get_external_fsharp(self) -> ::capnp::Result<crate::schema_capnp::option::Reader<'a,crate::schema_capnp::external::Owned>> {
+      ::capnp::traits::FromPointerReader::get_from_pointer(&self.reader.get_pointer_field(7), ::core::option::Option::None)
+    }
+    #[inline]
+    pub fn has_external_fsharp(&self) -> bool {
+      !self.reader.get_pointer_field(7).is_null()
+    }
   }
 
   pub struct Builder<'a> { builder: ::capnp::private::layout::StructBuilder<'a> }
@@ -1597,6 +1605,22 @@ pub mod type_constructor {
     pub fn has_documentation(&self) -> bool {
       !self.builder.get_pointer_field(6).is_null()
     }
+    #[inline]
+    pub fn get_external_fsharp(self) -> ::capnp::Result<crate::schema_capnp::option::Builder<'a,crate::schema_capnp::external::Owned>> {
+      ::capnp::traits::FromPointerBuilder::get_from_pointer(self.builder.get_pointer_field(7), ::core::option::Option::None)
+    }
+    #[inline]
+    pub fn set_external_fsharp(&mut self, value: crate::schema_capnp::option::Reader<'_,crate::schema_capnp::external::Owned>) -> ::capnp::Result<()> {
+      <crate::schema_capnp::option::Reader<'_,crate::schema_capnp::external::Owned> as ::capnp::traits::SetPointerBuilder>::set_pointer_builder(self.builder.get_pointer_field(7), value, false)
+    }
+    #[inline]
+    pub fn init_external_fsharp(self, ) -> crate::schema_capnp::option::Builder<'a,crate::schema_capnp::external::Owned> {
+      ::capnp::traits::FromPointerBuilder::init_pointer(self.builder.get_pointer_field(7), 0)
+    }
+    #[inline]
+    pub fn has_external_fsharp(&self) -> bool {
+      !self.builder.get_pointer_field(7).is_null()
+    }
   }
 
   pub struct Pipeline { _typeless: ::capnp::any_pointer::Pipeline }
@@ -1615,10 +1639,13 @@ pub mod type_constructor {
     pub fn get_origin(&self) -> crate::schema_capnp::src_span::Pipeline {
       ::capnp::capability::FromTypelessPipeline::new(self._typeless.get_pointer_field(5))
     }
+    pub fn get_external_fsharp(&self) -> crate::schema_capnp::option::Pipeline<crate::schema_capnp::external::Owned> {
+      ::capnp::capability::FromTypelessPipeline::new(self._typeless.get_pointer_field(7))
+    }
   }
   mod _private {
     use capnp::private::layout;
-    pub const STRUCT_SIZE: layout::StructSize = layout::StructSize { data: 0, pointers: 7 };
+    pub const STRUCT_SIZE: layout::StructSize = layout::StructSize { data: 0, pointers: 8 };
     pub const TYPE_ID: u64 = 0xb1fb_6d62_e00b_6d7a;
   }
 }
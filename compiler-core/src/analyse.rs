@@ -6,12 +6,12 @@ mod tests;
 
 use crate::{
     ast::{
-        self, Arg, BitArrayOption, CustomType, Definition, DefinitionLocation, Function,
-        GroupedStatements, Import, ModuleConstant, Publicity, RecordConstructor,
-        RecordConstructorArg, SrcSpan, Statement, TypeAlias, TypeAst, TypeAstConstructor,
-        TypeAstFn, TypeAstHole, TypeAstTuple, TypeAstVar, TypedDefinition, TypedExpr,
-        TypedFunction, TypedModule, UntypedArg, UntypedCustomType, UntypedFunction, UntypedImport,
-        UntypedModule, UntypedModuleConstant, UntypedStatement, UntypedTypeAlias,
+        self, Arg, BitArrayOption, CustomType, Definition, DefinitionLocation,
+        FSharpCallConvention, Function, GroupedStatements, Import, ModuleConstant, Publicity,
+        RecordConstructor, RecordConstructorArg, SrcSpan, Statement, TypeAlias, TypeAst,
+        TypeAstConstructor, TypeAstFn, TypeAstHole, TypeAstTuple, TypeAstVar, TypedDefinition,
+        TypedExpr, TypedFunction, TypedModule, UntypedArg, UntypedCustomType, UntypedFunction,
+        UntypedImport, UntypedModule, UntypedModuleConstant, UntypedStatement, UntypedTypeAlias,
     },
     build::{Origin, Outcome, Target},
     call_graph::{into_dependency_order, CallGraphNode},
@@ -479,6 +479,12 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         // Ensure that folks are not writing inline JavaScript expressions as
         // the implementation for JS externals.
         self.assert_valid_javascript_external(&name, external_javascript.as_ref(), location);
+        self.assert_valid_fsharp_external(
+            &name,
+            external_fsharp.as_ref(),
+            arguments.len(),
+            location,
+        );
 
         // Find the external implementation for the current target, if one has been given.
         let external = target_function_implementation(
@@ -698,6 +704,133 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         }
     }
 
+    /// Checks the function name of an `@external(fsharp, ...)` function is in
+    /// the form of one of the F# calling conventions, and that members of the
+    /// first argument are given it.
+    fn assert_valid_fsharp_external(
+        &mut self,
+        function_name: &EcoString,
+        external_fsharp: Option<&(EcoString, EcoString, SrcSpan)>,
+        arity: usize,
+        location: SrcSpan,
+    ) {
+        let Some((module, function, _location)) = external_fsharp else {
+            return;
+        };
+        let valid_arity = match FSharpCallConvention::parse(function) {
+            None => {
+                self.problems.error(Error::InvalidExternalFSharpFunction {
+                    location,
+                    function: function.clone(),
+                    name: function_name.clone(),
+                });
+                return;
+            }
+            // A constructor needs the name of the type to construct
+            Some((FSharpCallConvention::Constructor, _))
+                if crate::fsharp::is_external_file_path(module) =>
+            {
+                self.problems.error(Error::ExternalFSharpConstructorFile {
+                    location,
+                    module: module.clone(),
+                    name: function_name.clone(),
+                });
+                return;
+            }
+            Some((FSharpCallConvention::InstanceMethod, _)) => arity >= 1,
+            Some((FSharpCallConvention::PropertyGetter, _)) => arity == 1,
+            Some(_) => true,
+        };
+        if !valid_arity {
+            self.problems.error(Error::ExternalFSharpMemberArity {
+                location,
+                function: function.clone(),
+                name: function_name.clone(),
+                arity,
+            });
+        }
+    }
+
+    /// Checks the `@external(fsharp, ...)` attribute of a type, returning the
+    /// namespace and type name to store in the type's metadata if it is valid.
+    ///
+    /// A generic .NET type name may include its arity using the CLR's
+    /// backtick notation, such as ``Stack`1``, in which case it must match
+    /// the number of parameters of the Gleam type.
+    ///
+    /// Without that suffix the arity can't be checked here: the compiler
+    /// doesn't load the referenced .NET assemblies, so it can't tell whether
+    /// `Stack` is generic. The Gleam type's parameters are then passed as the
+    /// generic arguments as they are, and a mismatch is reported by the F#
+    /// compiler when the project is built.
+    fn validate_fsharp_external_type(
+        &mut self,
+        t: &UntypedCustomType,
+    ) -> Option<(EcoString, EcoString)> {
+        use regex::Regex;
+
+        static NAMESPACE: OnceLock<Regex> = OnceLock::new();
+        static TYPE: OnceLock<Regex> = OnceLock::new();
+
+        let (namespace, type_name, location) = t.external_fsharp.as_ref()?;
+        let location = *location;
+        let mut valid = true;
+
+        if !t.constructors.is_empty() {
+            self.problems.error(Error::ExternalTypeWithConstructors {
+                location,
+                name: t.name.clone(),
+            });
+            valid = false;
+        }
+
+        if !NAMESPACE
+            .get_or_init(|| {
+                Regex::new("^[a-zA-Z_][a-zA-Z0-9_]*(\\.[a-zA-Z_][a-zA-Z0-9_]*)*$").expect("regex")
+            })
+            .is_match(namespace)
+        {
+            self.problems.error(Error::InvalidExternalFSharpNamespace {
+                location,
+                namespace: namespace.clone(),
+                name: t.name.clone(),
+            });
+            valid = false;
+        }
+
+        let captures = TYPE
+            .get_or_init(|| Regex::new("^[a-zA-Z_][a-zA-Z0-9_]*(?:`([0-9]+))?$").expect("regex"))
+            .captures(type_name);
+        let Some(captures) = captures else {
+            self.problems.error(Error::InvalidExternalFSharpType {
+                location,
+                type_name: type_name.clone(),
+                name: t.name.clone(),
+            });
+            return None;
+        };
+
+        if let Some(arity) = captures.get(1) {
+            let expected = t.parameters.len();
+            let given = arity.as_str().parse().unwrap_or(usize::MAX);
+            if given != expected {
+                self.problems.error(Error::ExternalFSharpTypeArity {
+                    location,
+                    name: t.name.clone(),
+                    expected,
+                    given,
+                });
+                valid = false;
+            }
+        }
+
+        if valid {
+            Some((namespace.clone(), type_name.clone()))
+        } else {
+            None
+        }
+    }
+
     fn ensure_annotations_present(
         &mut self,
         arguments: &[UntypedArg],
@@ -821,6 +954,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             parameters,
             constructors,
             deprecation,
+            external_fsharp,
             ..
         } = t;
 
@@ -903,6 +1037,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             constructors,
             typed_parameters,
             deprecation,
+            external_fsharp,
         }))
     }
 
@@ -1102,6 +1237,8 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
 
         self.check_name_case(*name_location, name, Named::Type);
 
+        let external_fsharp = self.validate_fsharp_external_type(t);
+
         let mut hydrator = Hydrator::new();
         let parameters = self.make_type_vars(parameters, &mut hydrator, environment);
 
@@ -1142,6 +1279,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
                     publicity,
                     type_,
                     documentation: documentation.as_ref().map(|(_, doc)| doc.clone()),
+                    external_fsharp,
                 },
             )
             .expect("name uniqueness checked above");
@@ -1215,6 +1353,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
                     deprecation: deprecation.clone(),
                     publicity: *publicity,
                     documentation: documentation.as_ref().map(|(_, doc)| doc.clone()),
+                    external_fsharp: None,
                 },
             )?;
 
@@ -663,6 +663,77 @@ impl<T, E> Function<T, E> {
     }
 }
 
+/// How an `@external(fsharp, module, function)` function is called, given by
+/// the form of the function name.
+///
+/// ```gleam
+/// @external(fsharp, "List", "append")               // List.append a b
+/// @external(fsharp, "System.Math", "Max()")          // System.Math.Max(a, b)
+/// @external(fsharp, "System.Uri", "new")             // new System.Uri(a)
+/// @external(fsharp, "System.String", ".Contains()")  // a.Contains(b)
+/// @external(fsharp, "System.Uri", ".Host")           // a.Host
+/// @external(fsharp, "MyModule", "``my function``")   // MyModule.``my function`` a
+/// ```
+///
+/// The arguments are passed and the result returned without any conversion,
+/// so the Gleam types must match the .NET types exactly: a member taking or
+/// returning an `int` (rather than the `int64` used for `Int`) needs an F#
+/// wrapper function. Names are identifiers or double-backtick quoted
+/// identifiers; operators are not supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FSharpCallConvention {
+    /// A curried F# function, applied to each argument in turn.
+    Curried,
+    /// A static .NET method, given its arguments as a tuple.
+    StaticMethod,
+    /// A constructor of the .NET type named by the module.
+    Constructor,
+    /// A method of the first argument, given the rest as a tuple.
+    InstanceMethod,
+    /// A property of the only argument.
+    PropertyGetter,
+}
+
+impl FSharpCallConvention {
+    /// Parses the function name of an F# external into its calling convention
+    /// and the name of the function or member, returning `None` if it is not
+    /// valid.
+    pub fn parse(function: &str) -> Option<(Self, &str)> {
+        if function == "new" {
+            return Some((Self::Constructor, function));
+        }
+
+        let is_identifier = |name: &str| {
+            if let Some(quoted) = name
+                .strip_prefix("``")
+                .and_then(|name| name.strip_suffix("``"))
+            {
+                return !quoted.is_empty()
+                    && !quoted.contains('`')
+                    && !quoted.contains(['\n', '\r', '\t']);
+            }
+            let mut chars = name.chars();
+            chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '\'')
+        };
+
+        let (convention, name) = match (function.strip_prefix('.'), function.strip_suffix("()")) {
+            (Some(member), Some(_)) => (Self::InstanceMethod, &member[..member.len() - 2]),
+            (Some(member), None) => (Self::PropertyGetter, member),
+            (None, Some(name)) => (Self::StaticMethod, name),
+            (None, None) => (Self::Curried, function),
+        };
+
+        let valid = match convention {
+            Self::InstanceMethod | Self::PropertyGetter => is_identifier(name),
+            _ => name.split('.').all(is_identifier),
+        };
+        valid.then_some((convention, name))
+    }
+}
+
 pub type UntypedImport = Import<()>;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -760,6 +831,9 @@ pub struct CustomType<T> {
     /// Once type checked this field will contain the type information for the
     /// type parameters.
     pub typed_parameters: Vec<T>,
+    /// The .NET namespace and type name given with an
+    /// `@external(fsharp, ...)` attribute.
+    pub external_fsharp: Option<(EcoString, EcoString, SrcSpan)>,
 }
 
 impl<T> CustomType<T> {
@@ -158,8 +158,11 @@ pub enum TargetCodegenConfiguration {
     Erlang {
         app_file: Option<ErlangAppCodegenConfiguration>,
     },
-
-    FSharp,
+    FSharp {
+        /// Whether to compile the generated project with `dotnet build`. When
+        /// false only the F# sources and project files are written.
+        dotnet_build: bool,
+    },
 }
 
 pub struct FSharpCodegenConfiguration {
@@ -22,6 +22,7 @@ use crate::{
 };
 use askama::Template;
 use ecow::EcoString;
+use itertools::Itertools;
 use std::collections::HashSet;
 use std::fs;
 use std::{collections::HashMap, fmt::write, time::SystemTime};
@@ -31,6 +32,12 @@ use camino::{Utf8Path, Utf8PathBuf};
 
 use super::{ErlangAppCodegenConfiguration, TargetCodegenConfiguration, Telemetry};
 
+/// Written to the F# artefact directory after a successful `dotnet build`
+const DOTNET_BUILD_STAMP: &str = "dotnet_build.stamp";
+
+/// The errors from the last `dotnet build`, written by MSBuild's file logger
+const DOTNET_BUILD_LOG: &str = "dotnet_build.log";
+
 #[derive(Debug)]
 pub struct PackageCompiler<'a, IO> {
     pub io: IO,
@@ -147,6 +154,7 @@ where
         };
 
         // Load the cached modules that have previously been compiled
+        let mut cached_module_names = Vec::with_capacity(loaded.cached.len());
         for module in loaded.cached.into_iter() {
             // Emit any cached warnings.
             // Note that `self.cached_warnings` is set to `Ignore` (such as for
@@ -157,6 +165,7 @@ where
 
             // Register the cached module so its type information etc can be
             // used for compiling futher modules.
+            cached_module_names.push(module.name.clone());
             _ = existing_modules.insert(module.name.clone(), module);
         }
 
@@ -190,7 +199,12 @@ where
 
         tracing::debug!("performing_code_generation");
 
-        if let Err(error) = self.perform_codegen(&modules) {
+        let cached_modules = cached_module_names
+            .iter()
+            .filter_map(|name| existing_modules.get(name))
+            .collect_vec();
+
+        if let Err(error) = self.perform_codegen(&modules, &cached_modules) {
             return error.into();
         }
 
@@ -294,7 +308,11 @@ where
         Ok(())
     }
 
-    fn perform_codegen(&mut self, modules: &[Module]) -> Result<()> {
+    fn perform_codegen(
+        &mut self,
+        modules: &[Module],
+        cached_modules: &[&type_::ModuleInterface],
+    ) -> Result<()> {
         if !self.perform_codegen {
             tracing::debug!("skipping_codegen");
             return Ok(());
@@ -312,7 +330,9 @@ where
             TargetCodegenConfiguration::Erlang { app_file } => {
                 self.perform_erlang_codegen(modules, app_file.as_ref())
             }
-            TargetCodegenConfiguration::FSharp => self.perform_fsharp_codegen(modules),
+            TargetCodegenConfiguration::FSharp { dotnet_build } => {
+                self.perform_fsharp_codegen(modules, cached_modules, *dotnet_build)
+            }
         }
     }
 
@@ -388,7 +408,12 @@ where
         Ok(())
     }
 
-    fn perform_fsharp_codegen(&mut self, modules: &[Module]) -> Result<(), Error> {
+    fn perform_fsharp_codegen(
+        &mut self,
+        modules: &[Module],
+        cached_modules: &[&type_::ModuleInterface],
+        dotnet_build: bool,
+    ) -> Result<(), Error> {
         let input_dir = self.root.join("src");
         let output_dir = self.out.join(paths::ARTEFACT_DIRECTORY_NAME);
 
@@ -404,52 +429,80 @@ where
 
         tracing::info!("Generating F# code");
 
-        // Use the F# generator to render the modules
+        let fsharp_app = crate::codegen::FSharpApp::new(self.config, &input_dir, &output_dir);
+        let changed = fsharp_app.render(&io, modules, cached_modules)?;
 
-        let first_module = modules.first().expect("Must be at least one module");
-        let mut generator = crate::fsharp::Generator::new(
-            &self.config.name,
-            &first_module,
-            &first_module.input_path,
-            &self.config.fsharp,
-        );
-        let fsharp_app = crate::codegen::FSharpApp::new(&self.config, &input_dir, &output_dir);
-
-        fsharp_app.render(io, modules, &mut generator)?;
-        // Copy external files
-        for file in generator.external_files {
-            let file_name = file.file_name().expect("File name missing");
-            let output_file_path = output_dir.join("external").join(&file_name);
+        if !dotnet_build {
+            tracing::debug!("skipping_dotnet_build");
+            return Ok(());
+        }
 
-            let file_contents = fs::read_to_string(&file).expect(&format!(
-                "Could not read external file: {}",
-                &file.clone().as_str()
-            ));
-            self.io.write(&output_file_path, &file_contents)?;
+        // The stamp is only present if the last `dotnet build` succeeded, so
+        // a failed build is retried even if none of its inputs have changed.
+        let build_stamp = output_dir.join(DOTNET_BUILD_STAMP);
+        if !changed && io.is_file(&build_stamp) {
+            tracing::debug!("skipping_dotnet_build");
+            return Ok(());
+        }
+        if io.is_file(&build_stamp) {
+            io.delete_file(&build_stamp)?;
         }
-        // Implement F# compilation
+
         // TODO: Support additional build options
+        let build_log = output_dir.join(DOTNET_BUILD_LOG);
+        if io.is_file(&build_log) {
+            io.delete_file(&build_log)?;
+        }
 
         let status = self.io.exec(
             "dotnet",
             &[
                 String::from("build"),
                 format!("{}.fsproj", &self.config.name),
+                String::from("-fileLogger"),
+                format!("-fileLoggerParameters:LogFile={DOTNET_BUILD_LOG};ErrorsOnly"),
             ],
             &[],
             Some(&output_dir),
             self.subprocess_stdio,
         )?;
         if status != 0 {
-            return Err(Error::ShellCommand {
-                program: "dotnet".into(),
-                err: None,
-            });
+            return Err(self.dotnet_build_error(&build_log));
         }
 
+        io.write(&build_stamp, "")?;
+
         Ok(())
     }
 
+    /// The errors from a failed `dotnet build`. Errors in Gleam files, from
+    /// the `#line` directives in the generated F#, are located in the source.
+    fn dotnet_build_error(&self, build_log: &Utf8Path) -> Error {
+        let log = self.io.read(build_log).unwrap_or_default();
+
+        let errors = crate::fsharp::msbuild::parse_errors(&log)
+            .into_iter()
+            .map(|error| {
+                if error.path.extension() != Some("gleam") {
+                    return error;
+                }
+                match self.io.read(&error.path) {
+                    Ok(src) => error.with_source(src.into()),
+                    Err(_) => error,
+                }
+            })
+            .collect_vec();
+
+        if errors.is_empty() {
+            Error::ShellCommand {
+                program: "dotnet".into(),
+                err: None,
+            }
+        } else {
+            Error::FSharpBuild { errors }
+        }
+    }
+
     fn render_erlang_entrypoint_module(
         &mut self,
         out: &Utf8Path,
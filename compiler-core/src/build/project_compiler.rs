@@ -60,6 +60,9 @@ pub struct Options {
     pub warnings_as_errors: bool,
     pub root_target_support: TargetSupport,
     pub no_print_progress: bool,
+    /// Only generate F# for the fsharp target, without building it with
+    /// `dotnet build`
+    pub no_dotnet: bool,
 }
 
 #[derive(Debug)]
@@ -179,7 +182,7 @@ where
         // verify that this version is appropriate.
         self.check_gleam_version()?;
 
-        // The JavaScript target requires a prelude module to be written.
+        // The JavaScript and F# targets require a prelude module to be written.
         self.write_prelude()?;
 
         // Dependencies are compiled first.
@@ -256,15 +259,24 @@ where
     }
 
     fn write_prelude(&self) -> Result<()> {
-        // Only the JavaScript target has a prelude to write.
-        if !self.target().is_javascript() {
-            return Ok(());
-        }
-
         let build = self
             .paths
             .build_directory_for_target(self.mode(), self.target());
 
+        // The F# prelude is a project shared by all packages
+        if self.target() == Target::FSharp {
+            return crate::codegen::write_fsharp_prelude(
+                &self.io,
+                &build,
+                &self.config.fsharp.target_framework,
+            );
+        }
+
+        // The Erlang target has no prelude to write.
+        if !self.target().is_javascript() {
+            return Ok(());
+        }
+
         // Write the JavaScript prelude
         let path = build.join("prelude.mjs");
         if !self.io.is_file(&path) {
@@ -552,10 +564,9 @@ where
                 // This path is relative to each package output directory
                 prelude_location: Utf8PathBuf::from("../prelude.mjs"),
             },
-            Target::FSharp => {
-                // TODO: implement dotnet codegen
-                super::TargetCodegenConfiguration::FSharp {}
-            }
+            Target::FSharp => super::TargetCodegenConfiguration::FSharp {
+                dotnet_build: !self.options.no_dotnet,
+            },
         };
 
         let mut compiler = PackageCompiler::new(
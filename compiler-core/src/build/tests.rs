@@ -1,4 +1,18 @@
-use crate::{manifest::ManifestPackage, Error};
+use std::collections::HashSet;
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::{
+    build::{
+        Mode, NullTelemetry, PackageCompiler, StaleTracker, Target, TargetCodegenConfiguration,
+    },
+    config::PackageConfig,
+    io::{memory::InMemoryFileSystem, FileSystemReader, FileSystemWriter},
+    manifest::ManifestPackage,
+    uid::UniqueIdGenerator,
+    warning::WarningEmitter,
+    Error,
+};
 
 use super::project_compiler::{usable_build_tools, BuildTool};
 
@@ -55,3 +69,84 @@ fn usable_build_tool_mix_then_rebar3() {
         Ok(vec![BuildTool::Mix, BuildTool::Rebar3])
     )
 }
+
+fn compile_fsharp_package(fs: &InMemoryFileSystem, dotnet_build: bool) {
+    let config = PackageConfig {
+        name: "wibble".into(),
+        target: Target::FSharp,
+        ..Default::default()
+    };
+    let target = TargetCodegenConfiguration::FSharp { dotnet_build };
+    let root = Utf8PathBuf::from("/");
+    let out = Utf8PathBuf::from("/build/dev/fsharp/wibble");
+    let lib = Utf8PathBuf::from("/build/dev/fsharp");
+    let mut compiler = PackageCompiler::new(
+        &config,
+        Mode::Dev,
+        &root,
+        &out,
+        &lib,
+        &target,
+        UniqueIdGenerator::new(),
+        fs.clone(),
+    );
+    compiler.write_metadata = false;
+    let _ = compiler
+        .compile(
+            &WarningEmitter::null(),
+            &mut im::HashMap::new(),
+            &mut im::HashMap::new(),
+            &mut StaleTracker::default(),
+            &mut HashSet::new(),
+            &NullTelemetry,
+        )
+        .unwrap();
+}
+
+#[test]
+fn fsharp_package_without_dotnet_build() {
+    let fs = InMemoryFileSystem::new();
+    fs.write(Utf8Path::new("/src/wibble.gleam"), "pub fn main() { Nil }")
+        .unwrap();
+
+    compile_fsharp_package(&fs, false);
+
+    let artefacts = Utf8Path::new("/build/dev/fsharp/wibble/_gleam_artefacts");
+    assert!(fs.is_file(&artefacts.join("src/wibble.fs")));
+    assert!(fs.is_file(&artefacts.join("wibble.fsproj")));
+    assert_eq!(fs.executed_commands(), vec![]);
+}
+
+#[test]
+fn fsharp_package_dotnet_build_is_skipped_if_unchanged() {
+    let fs = InMemoryFileSystem::new();
+    fs.write(Utf8Path::new("/src/wibble.gleam"), "pub fn main() { Nil }")
+        .unwrap();
+    let stamp = Utf8Path::new("/build/dev/fsharp/wibble/_gleam_artefacts/dotnet_build.stamp");
+
+    compile_fsharp_package(&fs, true);
+    assert_eq!(fs.executed_commands().len(), 1);
+    assert_eq!(fs.executed_commands()[0].0, "dotnet");
+    assert!(fs.is_file(stamp));
+
+    // Nothing has changed since the last successful build
+    compile_fsharp_package(&fs, true);
+    assert_eq!(fs.executed_commands().len(), 1);
+}
+
+#[test]
+fn fsharp_package_dotnet_build_is_rerun_if_stamp_is_missing() {
+    let fs = InMemoryFileSystem::new();
+    fs.write(Utf8Path::new("/src/wibble.gleam"), "pub fn main() { Nil }")
+        .unwrap();
+    let stamp = Utf8Path::new("/build/dev/fsharp/wibble/_gleam_artefacts/dotnet_build.stamp");
+
+    compile_fsharp_package(&fs, true);
+    assert_eq!(fs.executed_commands().len(), 1);
+
+    // The previous build failed, so it is retried even though nothing changed
+    fs.delete_file(stamp).unwrap();
+    compile_fsharp_package(&fs, true);
+    assert_eq!(fs.executed_commands().len(), 2);
+    assert!(fs.is_file(stamp));
+}
@@ -1,19 +1,30 @@
 use crate::{
     analyse::TargetSupport,
-    build::{ErlangAppCodegenConfiguration, Module},
+    build::{ErlangAppCodegenConfiguration, Module, Origin},
     config::{FSharpOutputType, FSharpTestFramework, PackageConfig},
-    erlang, fsharp,
-    io::FileSystemWriter,
+    dep_tree, erlang,
+    error::{FileIoAction, FileKind},
+    fsharp::{
+        self,
+        project_file::{Item, ItemGroup, ProjectFile, PropertyGroup},
+        PRELUDE_PROJECT_NAME,
+    },
+    io::{FileSystemReader, FileSystemWriter},
     javascript,
     line_numbers::LineNumbers,
-    requirement::Requirement,
-    Result,
+    paths::ARTEFACT_DIRECTORY_NAME,
+    type_::ModuleInterface,
+    Error, Result,
 };
+use ecow::EcoString;
 use itertools::Itertools;
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug};
 
 use camino::{Utf8Path, Utf8PathBuf};
 
+#[cfg(test)]
+mod tests;
+
 /// A code generator that creates a .erl Erlang module and record header files
 /// for each Gleam module in the package.
 #[derive(Debug)]
@@ -170,59 +181,158 @@ impl<'a> FSharpApp<'a> {
         }
     }
 
-    pub fn render<Writer: FileSystemWriter>(
+    /// Writes the generated F# for the given modules along with the project
+    /// files. Modules loaded from the cache are not regenerated, but are still
+    /// included in the project files using their cached F# metadata.
+    ///
+    /// Files are only written if their content has changed, so their mtimes
+    /// are stable for MSBuild's incremental compilation. Returns whether any
+    /// file was written.
+    pub fn render<IO: FileSystemReader + FileSystemWriter>(
         &self,
-        writer: Writer,
+        io: &IO,
         modules: &'a [Module],
-        generator: &mut fsharp::Generator<'a>,
-    ) -> Result<()> {
-        // Write prelude file
-        let prelude_file_path = self.output_directory.join("gleam_prelude.fs");
-        writer.write(&prelude_file_path, fsharp::FSHARP_PRELUDE)?;
+        cached_modules: &[&ModuleInterface],
+    ) -> Result<bool> {
+        let mut changed = false;
 
-        // Write gleam.toml
+        // Write gleam.toml. Packages compiled without one, such as in the
+        // browser, have nothing to copy.
         let gleam_toml_path = self.input_dir.join("../gleam.toml");
-        let existing_gleam_toml = std::fs::read_to_string(&gleam_toml_path)
-            .expect(&format!("Failed to read {gleam_toml_path}"));
-        let output_gleam_toml_path = self.output_directory.join("gleam.toml");
-        writer.write(&output_gleam_toml_path, &existing_gleam_toml)?;
+        if io.is_file(&gleam_toml_path) {
+            let existing_gleam_toml = io.read(&gleam_toml_path)?;
+            let output_gleam_toml_path = self.output_directory.join("gleam.toml");
+            changed |= write_if_changed(io, &output_gleam_toml_path, &existing_gleam_toml)?;
+        }
+
+        let mut project_modules = Vec::with_capacity(modules.len() + cached_modules.len());
+        let mut external_files: HashMap<Utf8PathBuf, Origin> = HashMap::new();
+
+        // Cached modules were generated by a previous build
+        for module in cached_modules {
+            let cache = self.read_cache(io, &module.name)?;
+            for file in &cache.external_files {
+                add_external_file(&mut external_files, file.clone(), module.origin);
+            }
+            project_modules.push(ProjectModule {
+                name: module.name.clone(),
+                input_path: module.src_path.clone(),
+                origin: module.origin,
+                dependencies: cache.dependencies,
+            });
+        }
 
         // Write individual module files
-        for module in modules {
-            let module_file_path: Utf8PathBuf = self.module_output_path(module);
-            let module_content = generator.render_module(module, &module.input_path)?;
-            writer.write(&module_file_path, &module_content)?;
+        if let Some(first_module) = modules.first() {
+            let mut generator = fsharp::Generator::new(
+                &self.config.name,
+                first_module,
+                &first_module.input_path,
+                &self.config.fsharp,
+            );
+            generator.line_directives = true;
+
+            for module in modules {
+                let module_file_path = self.module_output_path(&module.name, &module.input_path);
+                let module_content = generator.render_module(module, &module.input_path)?;
+                changed |= write_if_changed(io, &module_file_path, &module_content)?;
+
+                let module_external_files = std::mem::take(&mut generator.external_files);
+                for (file, origin) in &module_external_files {
+                    add_external_file(&mut external_files, file.clone(), *origin);
+                }
+
+                let cache = FSharpCacheMetadata {
+                    dependencies: module
+                        .dependencies
+                        .iter()
+                        .map(|(name, _)| name.clone())
+                        .collect(),
+                    external_files: module_external_files.into_keys().sorted().collect(),
+                };
+                self.write_cache(io, &module.name, &cache)?;
+
+                project_modules.push(ProjectModule {
+                    name: module.name.clone(),
+                    input_path: module.input_path.clone(),
+                    origin: module.origin,
+                    dependencies: cache.dependencies,
+                });
+            }
         }
 
+        // F# requires files to be listed in dependency order
+        let project_modules = order_project_modules(project_modules);
+
         // TODO: Support conditionally outputting an exe or library
         // Create project file content
-        self.write_project_file(
-            &writer,
-            modules.iter().filter(|m| !m.is_test()),
-            generator,
+        changed |= self.write_project_file(
+            io,
+            project_modules.iter().filter(|m| m.origin.is_src()),
+            &external_files,
             false,
         )?;
 
-        self.write_project_file(&writer, modules.iter(), generator, true)?;
+        changed |= self.write_project_file(io, project_modules.iter(), &external_files, true)?;
 
-        Ok(())
+        // Copy external files
+        for file in external_files.keys() {
+            let file_name = file.file_name().expect("File name missing");
+            let output_file_path = self.output_directory.join("external").join(file_name);
+            let file_contents = io.read(file)?;
+            changed |= write_if_changed(io, &output_file_path, &file_contents)?;
+        }
+
+        Ok(changed)
+    }
+
+    fn cache_path(&self, module_name: &str) -> Utf8PathBuf {
+        self.output_directory
+            .join(format!("{}.cache_fsharp", module_name.replace("/", "@")))
     }
 
-    fn module_output_path(&self, module: &Module) -> Utf8PathBuf {
-        let parent_dir = module.input_path.parent().expect("must have a parent");
+    fn read_cache<IO: FileSystemReader>(
+        &self,
+        io: &IO,
+        module_name: &str,
+    ) -> Result<FSharpCacheMetadata> {
+        let path = self.cache_path(module_name);
+
+        // Caches written before F# metadata was cached will not have this
+        // file, so the module is treated as having no dependencies or
+        // external files.
+        if !io.is_file(&path) {
+            tracing::debug!(module = %module_name, "missing_fsharp_cache");
+            return Ok(FSharpCacheMetadata::default());
+        }
+
+        let bytes = io.read_bytes(&path)?;
+        FSharpCacheMetadata::from_binary(&bytes).map_err(|err| Error::FileIo {
+            kind: FileKind::File,
+            action: FileIoAction::Parse,
+            path,
+            err: Some(err),
+        })
+    }
+
+    fn write_cache<IO: FileSystemWriter>(
+        &self,
+        io: &IO,
+        module_name: &str,
+        cache: &FSharpCacheMetadata,
+    ) -> Result<()> {
+        io.write_bytes(&self.cache_path(module_name), &cache.to_binary())
+    }
 
-        let name = &module.name;
+    fn module_output_path(&self, name: &str, input_path: &Utf8Path) -> Utf8PathBuf {
+        let parent_dir = input_path.parent().expect("must have a parent");
 
-        let input_dir = self
-            .input_dir
-            .parent()
-            .expect("must have a parent")
-            .as_str();
+        let root_dir = self.input_dir.parent().expect("must have a parent");
 
-        let relative_module_path: Utf8PathBuf = parent_dir
-            .to_string()
-            .replace(input_dir, self.output_directory.as_str())
-            .into();
+        let relative_module_path = match parent_dir.strip_prefix(root_dir) {
+            Ok(relative) => self.output_directory.join(relative),
+            Err(_) => parent_dir.to_path_buf(),
+        };
 
         let file_name = if name.contains("/") {
             name.split("/").last().expect("must have a file name")
@@ -233,16 +343,54 @@ impl<'a> FSharpApp<'a> {
         relative_module_path.join(format!("{}.fs", file_name))
     }
 
-    fn write_project_file<Writer: FileSystemWriter>(
+    /// NuGet package metadata, taken from the Hex package metadata in gleam.toml
+    fn package_properties(&self) -> PropertyGroup {
+        let config = self.config;
+        let mut properties = PropertyGroup::labelled("Package")
+            .property("PackageId", config.name.as_str())
+            .property("Version", config.version.to_string());
+
+        // gleam.toml has no authors field so the repository owner is used,
+        // otherwise NuGet defaults to the package id
+        if let Some(user) = config.repository.user() {
+            properties.set("Authors", user.as_str());
+        }
+
+        if !config.description.is_empty() {
+            properties.set("Description", config.description.as_str());
+        }
+
+        if let Some(url) = config.repository.url() {
+            properties.set("RepositoryUrl", url);
+        }
+
+        if !config.licences.is_empty() {
+            let licences = config.licences.iter().map(|l| l.to_string()).join(" OR ");
+            properties.set("PackageLicenseExpression", licences);
+        }
+
+        properties
+    }
+
+    /// Paths in the project file are relative to the output directory so
+    /// that the generated project can be moved, e.g. into a Hex package.
+    fn relative_to_output_directory(&self, path: Utf8PathBuf) -> Utf8PathBuf {
+        match path.strip_prefix(self.output_directory) {
+            Ok(relative) => relative.to_path_buf(),
+            Err(_) => path,
+        }
+    }
+
+    fn write_project_file<'m, IO: FileSystemReader + FileSystemWriter>(
         &self,
-        writer: &Writer,
-        modules: impl Iterator<Item = &'a Module>,
-        generator: &mut fsharp::Generator<'a>,
+        io: &IO,
+        modules: impl Iterator<Item = &'m ProjectModule>,
+        external_files: &HashMap<Utf8PathBuf, Origin>,
         test_project: bool,
-    ) -> Result<()> {
+    ) -> Result<bool> {
         let modules = modules.collect::<Vec<_>>();
         if modules.is_empty() {
-            return Ok(());
+            return Ok(false);
         }
 
         let project_file_path = if test_project {
@@ -253,136 +401,262 @@ impl<'a> FSharpApp<'a> {
                 .join(format!("{}.fsproj", &self.config.name))
         };
 
-        let external_files = generator
-            .external_files
-            .iter()
-            .filter(|file| {
-                if !test_project {
-                    !file.to_string().contains("test")
-                } else {
-                    true
-                }
-            })
-            .map(|file| {
-                format!(
-                    "<Compile Include=\"./external/{}\" />",
-                    file.file_name().expect("Missing file name")
-                )
-            })
-            .collect::<Vec<_>>()
-            .join("\n    ");
-
-        let source_files = modules
+        let project_file = self.project_file(&modules, external_files, test_project);
+        write_if_changed(io, &project_file_path, &project_file.to_xml())
+    }
+
+    fn project_file(
+        &self,
+        modules: &[&ProjectModule],
+        external_files: &HashMap<Utf8PathBuf, Origin>,
+        test_project: bool,
+    ) -> ProjectFile {
+        let fsharp = &self.config.fsharp;
+        let mut project = ProjectFile::new("Microsoft.NET.Sdk");
+
+        let output_property = if test_project {
+            ("IsPackable", "false")
+        } else if fsharp.output_type == FSharpOutputType::Exe {
+            ("OutputType", "Exe")
+        } else {
+            ("OutputType", "Library")
+        };
+
+        project.property_groups.push(
+            PropertyGroup::new()
+                .property("TargetFramework", fsharp.target_framework.as_str())
+                .property("RootNamespace", self.config.name.as_str())
+                .property("IncludeDocumentation", "true")
+                .property("NoWarn", "$(NoWarn);FS0020;")
+                .property(output_property.0, output_property.1),
+        );
+
+        if !test_project {
+            project.property_groups.push(self.package_properties());
+        }
+
+        project
+            .item_groups
+            .push(ItemGroup::new().item(Item::new("None", "gleam.toml")));
+
+        let mut compiled = ItemGroup::labelled("Modules");
+        for file in external_files
             .iter()
-            .map(|m| {
-                let module_file_path = self.module_output_path(m);
-                format!("<Compile Include=\"{module_file_path}\" />")
-            })
-            .collect::<Vec<_>>()
-            .join("\n    ");
-
-        let project_references = self
+            .filter(|(_, origin)| test_project || **origin == Origin::Src)
+            .map(|(file, _)| file)
+            .sorted()
+        {
+            let file_name = file.file_name().expect("Missing file name");
+            compiled = compiled.item(Item::new("Compile", format!("./external/{file_name}")));
+        }
+        for module in modules {
+            let module_file_path = self.relative_to_output_directory(
+                self.module_output_path(&module.name, &module.input_path),
+            );
+            compiled = compiled.item(Item::new("Compile", module_file_path.as_str()));
+        }
+        project.item_groups.push(compiled);
+
+        // Every package, including dependencies from Hex and git, is compiled
+        // into its own project in the build directory alongside this one.
+        let mut project_references = ItemGroup::labelled("ProjectReferences").item(Item::new(
+            "ProjectReference",
+            package_project_path(PRELUDE_PROJECT_NAME),
+        ));
+        let dev_dependencies = if test_project {
+            Some(&self.config.dev_dependencies)
+        } else {
+            None
+        };
+        for name in self
             .config
             .dependencies
-            .iter()
-            .map(|(name, requirement)| match requirement {
-                Requirement::Path { path } => {
-                    let project_name = path
-                        .as_str()
-                        .split("/")
-                        .last()
-                        .expect("Missing dependency project name");
-
-                    format!(
-                        "<ProjectReference Include=\"../{}/_gleam_artefacts/{}.fsproj\" />",
-                        path.as_str(),
-                        project_name
-                    )
-                }
-                _ => format!("<ProjectReference Include=\"{}\" />", name),
-            })
-            .collect::<Vec<_>>()
-            .join("\n    ");
+            .keys()
+            .chain(dev_dependencies.into_iter().flat_map(|deps| deps.keys()))
+            .sorted()
+        {
+            project_references =
+                project_references.item(Item::new("ProjectReference", package_project_path(name)));
+        }
+        project.item_groups.push(project_references);
 
-        let package_references = self
-            .config
-            .fsharp
-            .package_references
-            .iter()
-            .map(|(name, version)| {
-                format!(
-                    "<PackageReference Include=\"{}\" Version=\"{}\" />",
-                    name, version
-                )
-            })
-            .collect::<Vec<_>>()
-            .join("\n    ");
-
-        let root_namespace = &self.config.name;
-
-        let target_framework = self.config.fsharp.target_framework.as_str();
-
-        let sdk = "Microsoft.NET.Sdk";
-
-        let package_references = if test_project {
-            match &self.config.fsharp.test_framework {
-                FSharpTestFramework::XUnit {
-                    version,
-                    runner_version,
-                } => {
-                    format!(
-                        "{package_references}
-    <PackageReference Include=\"Microsoft.NET.Test.Sdk\" Version=\"17.11.0\" />
-    <PackageReference Include=\"xunit\" Version=\"{version}\" />
-    <PackageReference Include=\"xunit.runner.visualstudio\" Version=\"{runner_version}\">
-        <IncludeAssets>runtime; build; native; contentfiles; analyzers; buildtransitive</IncludeAssets>
-        <PrivateAssets>all</PrivateAssets>
-    </PackageReference>"
-                    )
+        let mut package_references = ItemGroup::labelled("PackageReferences");
+        for (name, version) in fsharp.package_references.iter().sorted() {
+            package_references = package_references
+                .item(Item::new("PackageReference", name).attribute("Version", version));
+        }
+        if test_project {
+            for item in self.test_package_references() {
+                package_references = package_references.item(item);
+            }
+        }
+        project.item_groups.push(package_references);
+
+        // User supplied properties and items from gleam.toml
+        for (name, value) in fsharp.properties.iter() {
+            project.set_property(name, value.to_string());
+        }
+        for (kind, items) in fsharp.item_groups.iter() {
+            for item in items {
+                let mut merged = Item::new(kind, item.include.as_str());
+                for (name, value) in item.attributes.iter() {
+                    merged.set_attribute(name, value.to_string());
                 }
+                project.merge_item("gleam.toml", merged);
             }
-        } else {
-            package_references
-        };
+        }
 
-        let additional_properties = if test_project {
-            "<IsPackable>false</IsPackable>"
-        } else if self.config.fsharp.output_type == FSharpOutputType::Exe {
-            "<OutputType>Exe</OutputType>"
-        } else {
-            "<OutputType>Library</OutputType>"
-        };
+        project
+    }
 
-        let prelude_file_path = self.output_directory.join("gleam_prelude.fs");
-
-        let project_file_content = format!(
-            r#"<Project Sdk="{sdk}">
-  <PropertyGroup>
-    <TargetFramework>{target_framework}</TargetFramework>
-    <RootNamespace>{root_namespace}</RootNamespace>
-    <IncludeDocumentation>true</IncludeDocumentation>
-    <NoWarn>$(NoWarn);FS0020;</NoWarn>
-    {additional_properties}
-  </PropertyGroup>
-  <ItemGroup>
-    <None Include="gleam.toml" />
-  </ItemGroup>
-  <ItemGroup Label="Modules">
-    <Compile Include="{prelude_file_path}" />
-    {external_files}
-    {source_files}
-  </ItemGroup>
-  <ItemGroup Label="ProjectReferences">
-    {project_references}
-  </ItemGroup>
-  <ItemGroup Label="PackageReferences">
-    {package_references}
-  </ItemGroup>
-</Project>
-"#
-        );
+    fn test_package_references(&self) -> Vec<Item> {
+        let test_sdk =
+            Item::new("PackageReference", "Microsoft.NET.Test.Sdk").attribute("Version", "17.11.0");
+
+        match &self.config.fsharp.test_framework {
+            FSharpTestFramework::XUnit {
+                version,
+                runner_version,
+            } => vec![
+                test_sdk,
+                Item::new("PackageReference", "xunit").attribute("Version", version),
+                Item::new("PackageReference", "xunit.runner.visualstudio")
+                    .attribute("Version", runner_version)
+                    .metadata(
+                        "IncludeAssets",
+                        "runtime; build; native; contentfiles; analyzers; buildtransitive",
+                    )
+                    .metadata("PrivateAssets", "all"),
+            ],
+            FSharpTestFramework::NUnit {
+                version,
+                adapter_version,
+            } => vec![
+                test_sdk,
+                Item::new("PackageReference", "NUnit").attribute("Version", version),
+                Item::new("PackageReference", "NUnit3TestAdapter")
+                    .attribute("Version", adapter_version),
+            ],
+            FSharpTestFramework::MsTest {
+                version,
+                adapter_version,
+            } => vec![
+                test_sdk,
+                Item::new("PackageReference", "MSTest.TestFramework").attribute("Version", version),
+                Item::new("PackageReference", "MSTest.TestAdapter")
+                    .attribute("Version", adapter_version),
+            ],
+        }
+    }
+}
+
+/// A module to be included in the F# project files
+#[derive(Debug)]
+struct ProjectModule {
+    name: EcoString,
+    input_path: Utf8PathBuf,
+    origin: Origin,
+    dependencies: Vec<EcoString>,
+}
+
+/// The path to the project file of a package in the same build directory,
+/// relative to the output directory of another package.
+pub fn package_project_path(name: &str) -> String {
+    format!("../../{name}/{ARTEFACT_DIRECTORY_NAME}/{name}.fsproj")
+}
+
+/// The F# prelude is compiled once into its own project which the projects
+/// of all packages reference, so that values of the prelude types can be
+/// passed between packages.
+pub fn write_fsharp_prelude<IO: FileSystemReader + FileSystemWriter>(
+    io: &IO,
+    build_directory: &Utf8Path,
+    target_framework: &str,
+) -> Result<()> {
+    let output_directory = build_directory
+        .join(PRELUDE_PROJECT_NAME)
+        .join(ARTEFACT_DIRECTORY_NAME);
+    let source_file = format!("{PRELUDE_PROJECT_NAME}.fs");
+
+    let mut project = ProjectFile::new("Microsoft.NET.Sdk");
+    project.property_groups.push(
+        PropertyGroup::new()
+            .property("TargetFramework", target_framework)
+            .property("RootNamespace", "gleam")
+            .property("OutputType", "Library"),
+    );
+    project
+        .item_groups
+        .push(ItemGroup::labelled("Modules").item(Item::new("Compile", source_file.as_str())));
+
+    let _ = write_if_changed(
+        io,
+        &output_directory.join(&source_file),
+        fsharp::FSHARP_PRELUDE,
+    )?;
+    let _ = write_if_changed(
+        io,
+        &output_directory.join(format!("{PRELUDE_PROJECT_NAME}.fsproj")),
+        &project.to_xml(),
+    )?;
+    Ok(())
+}
+
+fn order_project_modules(modules: Vec<ProjectModule>) -> Vec<ProjectModule> {
+    let deps = modules
+        .iter()
+        .map(|m| (m.name.clone(), m.dependencies.clone()))
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        .collect();
+    let sequence = dep_tree::toposort_deps(deps)
+        .expect("Module dependencies have already been checked for cycles");
+    let mut modules: HashMap<_, _> = modules.into_iter().map(|m| (m.name.clone(), m)).collect();
+    sequence
+        .into_iter()
+        .filter_map(|name| modules.remove(&name))
+        .collect()
+}
+
+fn add_external_file(
+    external_files: &mut HashMap<Utf8PathBuf, Origin>,
+    file: Utf8PathBuf,
+    origin: Origin,
+) {
+    let existing = external_files.entry(file).or_insert(origin);
+    if origin.is_src() {
+        *existing = Origin::Src;
+    }
+}
+
+/// Writes the file only if its content differs from what is already on disk,
+/// returning whether it was written.
+fn write_if_changed<IO: FileSystemReader + FileSystemWriter>(
+    io: &IO,
+    path: &Utf8Path,
+    content: &str,
+) -> Result<bool> {
+    if io.is_file(path) && io.read(path)? == content {
+        return Ok(false);
+    }
+    io.write(path, content)?;
+    Ok(true)
+}
+
+/// F# specific information about a generated module, cached so that the
+/// project files can be written without regenerating unchanged modules.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct FSharpCacheMetadata {
+    dependencies: Vec<EcoString>,
+    external_files: Vec<Utf8PathBuf>,
+}
+
+impl FSharpCacheMetadata {
+    fn to_binary(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("Serializing F# cache info")
+    }
 
-        writer.write(&project_file_path, &project_file_content)
+    fn from_binary(bytes: &[u8]) -> Result<Self, String> {
+        bincode::deserialize(bytes).map_err(|e| e.to_string())
     }
 }
 
@@ -0,0 +1,276 @@
+use super::{
+    order_project_modules, write_fsharp_prelude, write_if_changed, FSharpApp, ProjectModule,
+};
+use crate::{
+    build::Origin,
+    config::PackageConfig,
+    io::{memory::InMemoryFileSystem, FileSystemReader, FileSystemWriter},
+};
+use camino::{Utf8Path, Utf8PathBuf};
+use itertools::Itertools;
+use std::{
+    collections::HashMap,
+    time::{Duration, UNIX_EPOCH},
+};
+
+fn project_module(name: &str, dependencies: &[&str]) -> ProjectModule {
+    ProjectModule {
+        name: name.into(),
+        input_path: Utf8PathBuf::from(format!("/wibble/src/{name}.gleam")),
+        origin: Origin::Src,
+        dependencies: dependencies.iter().map(|d| (*d).into()).collect(),
+    }
+}
+
+fn fsharp_project_file(config: &str, test_project: bool) -> String {
+    let config: PackageConfig = toml::from_str(config).expect("Invalid gleam.toml");
+    fsharp_project_file_for_config(&config, test_project)
+}
+
+fn fsharp_project_file_for_config(config: &PackageConfig, test_project: bool) -> String {
+    let input_dir = Utf8PathBuf::from("/wibble/src");
+    let output_dir = Utf8PathBuf::from("/wibble/build/dev/fsharp/wibble/_gleam_artefacts");
+    let app = FSharpApp::new(config, &input_dir, &output_dir);
+
+    let wibble = project_module("wibble", &[]);
+    let wobble = project_module("wibble/wobble", &["wibble"]);
+    let wibble_test = ProjectModule {
+        name: "wibble_test".into(),
+        input_path: Utf8PathBuf::from("/wibble/test/wibble_test.gleam"),
+        origin: Origin::Test,
+        dependencies: vec!["wibble".into()],
+    };
+    let external_files = HashMap::from([
+        (Utf8PathBuf::from("/wibble/src/ffi.fs"), Origin::Src),
+        (Utf8PathBuf::from("/wibble/test/test_ffi.fs"), Origin::Test),
+    ]);
+
+    let modules = if test_project {
+        vec![&wibble, &wobble, &wibble_test]
+    } else {
+        vec![&wibble, &wobble]
+    };
+    app.project_file(&modules, &external_files, test_project)
+        .to_xml()
+}
+
+#[test]
+fn fsharp_file_is_written_if_missing() {
+    let fs = InMemoryFileSystem::new();
+    let path = Utf8Path::new("/out/wibble.fs");
+
+    assert!(write_if_changed(&fs, path, "1").unwrap());
+    assert_eq!(fs.read(path).unwrap(), "1");
+}
+
+#[test]
+fn fsharp_file_is_not_rewritten_if_unchanged() {
+    let fs = InMemoryFileSystem::new();
+    let path = Utf8Path::new("/out/wibble.fs");
+    fs.write(path, "1").unwrap();
+    let mtime = UNIX_EPOCH + Duration::from_secs(1);
+    fs.set_modification_time(path, mtime);
+
+    assert!(!write_if_changed(&fs, path, "1").unwrap());
+    assert_eq!(fs.modification_time(path).unwrap(), mtime);
+}
+
+#[test]
+fn fsharp_file_is_rewritten_if_changed() {
+    let fs = InMemoryFileSystem::new();
+    let path = Utf8Path::new("/out/wibble.fs");
+    fs.write(path, "1").unwrap();
+
+    assert!(write_if_changed(&fs, path, "2").unwrap());
+    assert_eq!(fs.read(path).unwrap(), "2");
+}
+
+#[test]
+fn fsharp_project_modules_are_in_dependency_order() {
+    let modules = vec![
+        project_module("wibble", &["wobble", "gleam/list"]),
+        project_module("wubble", &[]),
+        project_module("wobble", &["wubble"]),
+    ];
+
+    let names = order_project_modules(modules)
+        .into_iter()
+        .map(|m| m.name)
+        .collect_vec();
+
+    assert_eq!(names, vec!["wubble", "wobble", "wibble"]);
+}
+
+#[test]
+fn fsharp_library_project_file() {
+    insta::assert_snapshot!(fsharp_project_file(
+        r#"
+name = "wibble"
+version = "1.2.0"
+description = "A <wibbly> package"
+licences = ["Apache-2.0", "MIT"]
+repository = { type = "github", user = "lpil", repo = "wibble" }
+
+[dependencies]
+wobble = { path = "../wobble" }
+
+[fsharp.package_references]
+"FSharp.Data" = "6.4.0"
+"#,
+        false
+    ));
+}
+
+#[test]
+fn fsharp_executable_project_file() {
+    insta::assert_snapshot!(fsharp_project_file(
+        r#"
+name = "wibble"
+
+[fsharp]
+output_type = "Exe"
+target_framework = "net9.0"
+"#,
+        false
+    ));
+}
+
+#[test]
+fn fsharp_native_project_file() {
+    let mut config: PackageConfig = toml::from_str(
+        r#"
+name = "wibble"
+
+[fsharp.properties]
+PublishTrimmed = false
+"#,
+    )
+    .expect("Invalid gleam.toml");
+    config.fsharp.publish_native();
+
+    insta::assert_snapshot!(fsharp_project_file_for_config(&config, false));
+}
+
+#[test]
+fn fsharp_test_project_file() {
+    insta::assert_snapshot!(fsharp_project_file(
+        r#"
+name = "wibble"
+"#,
+        true
+    ));
+}
+
+#[test]
+fn fsharp_project_file_with_properties_and_items() {
+    insta::assert_snapshot!(fsharp_project_file(
+        r#"
+name = "wibble"
+
+[fsharp.properties]
+Nullable = "enable"
+LangVersion = "preview"
+TreatWarningsAsErrors = true
+InvariantGlobalization = true
+NoWarn = "$(NoWarn);FS0020;FS3370"
+
+[fsharp.item_groups]
+InternalsVisibleTo = [{ Include = "wibble_test" }]
+PackageReference = [
+  { Include = "Microsoft.NET.Test.Sdk", Version = "17.12.0" },
+  { Include = "FsCheck", Version = "2.16.6" },
+]
+"#,
+        true
+    ));
+}
+
+#[test]
+fn fsharp_project_file_references_hex_and_git_dependencies() {
+    insta::assert_snapshot!(fsharp_project_file(
+        r#"
+name = "wibble"
+
+[dependencies]
+gleam_stdlib = ">= 0.36.0 and < 1.0.0"
+gleam_json = { git = "https://github.com/gleam-lang/json", ref = "main" }
+
+[dev-dependencies]
+gleeunit = ">= 1.0.0 and < 2.0.0"
+"#,
+        false
+    ));
+}
+
+#[test]
+fn fsharp_test_project_file_references_dev_dependencies() {
+    insta::assert_snapshot!(fsharp_project_file(
+        r#"
+name = "wibble"
+
+[dependencies]
+gleam_stdlib = ">= 0.36.0 and < 1.0.0"
+
+[dev-dependencies]
+gleeunit = ">= 1.0.0 and < 2.0.0"
+"#,
+        true
+    ));
+}
+
+#[test]
+fn fsharp_prelude_project_is_written() {
+    let fs = InMemoryFileSystem::new();
+    write_fsharp_prelude(&fs, Utf8Path::new("/build/dev/fsharp"), "net8.0").unwrap();
+
+    let directory = Utf8Path::new("/build/dev/fsharp/gleam_prelude/_gleam_artefacts");
+    assert_eq!(
+        fs.read(&directory.join("gleam_prelude.fs")).unwrap(),
+        crate::fsharp::FSHARP_PRELUDE
+    );
+    insta::assert_snapshot!(fs.read(&directory.join("gleam_prelude.fsproj")).unwrap());
+}
+
+#[test]
+fn fsharp_project_file_package_properties() {
+    let project = fsharp_project_file(
+        r#"
+name = "wibble"
+version = "1.2.0"
+licences = ["Apache-2.0"]
+repository = { type = "gitlab", user = "lpil", repo = "wibble" }
+"#,
+        false,
+    );
+
+    assert!(project.contains(
+        r#"  <PropertyGroup Label="Package">
+    <PackageId>wibble</PackageId>
+    <Version>1.2.0</Version>
+    <Authors>lpil</Authors>
+    <RepositoryUrl>https://gitlab.com/lpil/wibble</RepositoryUrl>
+    <PackageLicenseExpression>Apache-2.0</PackageLicenseExpression>
+  </PropertyGroup>"#
+    ));
+}
+
+#[test]
+fn fsharp_project_file_package_properties_without_repository_or_licences() {
+    let project = fsharp_project_file(
+        r#"
+name = "wibble"
+version = "1.2.0"
+"#,
+        false,
+    );
+
+    assert!(project.contains(
+        r#"  <PropertyGroup Label="Package">
+    <PackageId>wibble</PackageId>
+    <Version>1.2.0</Version>
+  </PropertyGroup>"#
+    ));
+    assert!(!project.contains("<Authors>"));
+    assert!(!project.contains("<RepositoryUrl>"));
+    assert!(!project.contains("<PackageLicenseExpression>"));
+}
@@ -10,7 +10,7 @@ use globset::{Glob, GlobSetBuilder};
 use hexpm::version::{self, Version};
 use http::Uri;
 use serde::Deserialize;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::{self};
 use std::marker::PhantomData;
 
@@ -674,29 +674,74 @@ pub struct JavaScriptConfig {
 #[derive(Deserialize, Debug, PartialEq, Clone)]
 pub enum FSharpTestFramework {
     XUnit {
+        #[serde(default = "default_xunit_version")]
         version: String,
+        #[serde(default = "default_xunit_runner_version")]
         runner_version: String,
     },
-    // NUnit,
-    // MsTest,
+    NUnit {
+        #[serde(default = "default_nunit_version")]
+        version: String,
+        #[serde(default = "default_nunit_adapter_version")]
+        adapter_version: String,
+    },
+    MsTest {
+        #[serde(default = "default_mstest_version")]
+        version: String,
+        #[serde(default = "default_mstest_version")]
+        adapter_version: String,
+    },
 }
 
 impl Default for FSharpTestFramework {
     fn default() -> Self {
         Self::XUnit {
-            version: "2.5.2".into(),
-            runner_version: "2.5.3".into(),
+            version: default_xunit_version(),
+            runner_version: default_xunit_runner_version(),
         }
     }
 }
 
+fn default_xunit_version() -> String {
+    String::from("2.5.2")
+}
+
+fn default_xunit_runner_version() -> String {
+    String::from("2.5.3")
+}
+
+fn default_nunit_version() -> String {
+    String::from("4.2.2")
+}
+
+fn default_nunit_adapter_version() -> String {
+    String::from("4.6.0")
+}
+
+fn default_mstest_version() -> String {
+    String::from("3.6.1")
+}
+
 #[derive(Deserialize, Debug, PartialEq, Clone, Copy)]
 pub enum FSharpOutputType {
     Library,
     Exe,
 }
 
+/// How the Gleam `Int` type is represented in generated F# code
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FSharpIntRepresentation {
+    /// 64-bit integers, which are fast but can overflow
+    #[default]
+    Int64,
+    /// `System.Numerics.BigInteger`, which matches the arbitrary precision
+    /// integers of the Erlang target
+    BigInt,
+}
+
 #[derive(Deserialize, Debug, PartialEq, Clone)]
+#[serde(remote = "Self")]
 pub struct FSharpConfig {
     #[serde(default = "default_fsharp_target_framework")]
     pub target_framework: String,
@@ -706,17 +751,82 @@ pub struct FSharpConfig {
     #[serde(default, rename = "package_references")]
     pub package_references: HashMap<String, String>,
 
-    /// Type mappings for F#
-    /// Use this section to tell the compiler how to map external types to F# types.
-    /// If the type is generic, make sure to include them in both the key and value.
-    #[serde(default, rename = "type_mappings")]
-    pub type_mappings: HashMap<String, String>,
-
     #[serde(default)]
     pub test_framework: FSharpTestFramework,
 
     #[serde(default = "default_fsharp_output_type")]
     pub output_type: FSharpOutputType,
+
+    #[serde(default)]
+    pub int_representation: FSharpIntRepresentation,
+
+    /// MSBuild properties added to the generated project files, replacing
+    /// any generated property with the same name.
+    #[serde(default)]
+    pub properties: BTreeMap<String, FSharpPropertyValue>,
+
+    /// MSBuild items added to the generated project files, keyed by item
+    /// type. Items which include the same path as a generated item of the same
+    /// type are merged into it.
+    #[serde(default)]
+    pub item_groups: BTreeMap<String, Vec<FSharpItem>>,
+
+    /// Whether to generate an `Interop` class in each module, exposing its
+    /// public functions in a form that is convenient to call from C#
+    #[serde(default)]
+    pub csharp_interop: bool,
+}
+
+impl<'de> Deserialize<'de> for FSharpConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct FSharpConfigToml {
+            #[serde(flatten, with = "FSharpConfig")]
+            config: FSharpConfig,
+            type_mappings: Option<serde::de::IgnoredAny>,
+        }
+
+        // `[fsharp.type_mappings]` has been replaced by `@external(fsharp, ...)`
+        // custom types, so it is rejected rather than silently ignored.
+        let toml = FSharpConfigToml::deserialize(deserializer)?;
+        if toml.type_mappings.is_some() {
+            return Err(serde::de::Error::custom(
+                "`type_mappings` is no longer supported. Annotate a custom type with \
+`@external(fsharp, \"Namespace\", \"Type\")` to use a .NET type instead",
+            ));
+        }
+        Ok(toml.config)
+    }
+}
+
+/// The value of an MSBuild property or item attribute
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(untagged)]
+pub enum FSharpPropertyValue {
+    String(String),
+    Bool(bool),
+    Int(i64),
+}
+
+impl fmt::Display for FSharpPropertyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FSharpPropertyValue::String(value) => f.write_str(value),
+            FSharpPropertyValue::Bool(value) => write!(f, "{value}"),
+            FSharpPropertyValue::Int(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct FSharpItem {
+    #[serde(rename = "Include")]
+    pub include: String,
+    #[serde(flatten)]
+    pub attributes: BTreeMap<String, FSharpPropertyValue>,
 }
 
 // TODO: I don't think this should be necessary, but the default values are not being applied sometimes for some reason
@@ -725,9 +835,27 @@ impl Default for FSharpConfig {
         Self {
             target_framework: default_fsharp_target_framework(),
             package_references: Default::default(),
-            type_mappings: Default::default(),
             test_framework: Default::default(),
             output_type: default_fsharp_output_type(),
+            int_representation: Default::default(),
+            properties: Default::default(),
+            item_groups: Default::default(),
+            csharp_interop: false,
+        }
+    }
+}
+
+impl FSharpConfig {
+    /// Configures the project for `gleam export dotnet-native`. Native AOT
+    /// requires an executable, and trimming keeps the binary small unless
+    /// the package has configured these properties itself.
+    pub fn publish_native(&mut self) {
+        self.output_type = FSharpOutputType::Exe;
+        for property in ["PublishAot", "PublishTrimmed"] {
+            let _ = self
+                .properties
+                .entry(property.into())
+                .or_insert(FSharpPropertyValue::Bool(true));
         }
     }
 }
@@ -746,6 +874,135 @@ fn fsharp_config_default_target_framework() {
     assert_eq!(config.target_framework, "net8.0");
 }
 
+#[test]
+fn fsharp_config_default_int_representation() {
+    let config: FSharpConfig = toml::from_str("").unwrap();
+    assert_eq!(config.int_representation, FSharpIntRepresentation::Int64);
+}
+
+#[test]
+fn fsharp_config_bigint_int_representation() {
+    let config: FSharpConfig = toml::from_str(r#"int_representation = "bigint""#).unwrap();
+    assert_eq!(config.int_representation, FSharpIntRepresentation::BigInt);
+}
+
+#[test]
+fn fsharp_config_csharp_interop() {
+    let config: FSharpConfig = toml::from_str("").unwrap();
+    assert!(!config.csharp_interop);
+    let config: FSharpConfig = toml::from_str("csharp_interop = true").unwrap();
+    assert!(config.csharp_interop);
+}
+
+#[test]
+fn fsharp_config_type_mappings_are_rejected() {
+    let input = r#"
+[type_mappings]
+Stack = "System.Collections.Generic.Stack"
+"#;
+    let error = toml::from_str::<FSharpConfig>(input)
+        .expect_err("type_mappings should be rejected")
+        .to_string();
+    assert!(error.contains("`type_mappings` is no longer supported"));
+    assert!(error.contains("@external(fsharp"));
+}
+
+#[test]
+fn fsharp_config_default_test_framework() {
+    let config: FSharpConfig = toml::from_str("").unwrap();
+    assert_eq!(
+        config.test_framework,
+        FSharpTestFramework::XUnit {
+            version: "2.5.2".into(),
+            runner_version: "2.5.3".into(),
+        }
+    );
+}
+
+#[test]
+fn fsharp_config_nunit_test_framework() {
+    let input = r#"
+[test_framework.NUnit]
+version = "4.1.0"
+"#;
+    let config: FSharpConfig = toml::from_str(input).unwrap();
+    assert_eq!(
+        config.test_framework,
+        FSharpTestFramework::NUnit {
+            version: "4.1.0".into(),
+            adapter_version: "4.6.0".into(),
+        }
+    );
+}
+
+#[test]
+fn fsharp_config_mstest_test_framework() {
+    let input = r#"
+[test_framework.MsTest]
+version = "3.5.0"
+adapter_version = "3.5.1"
+"#;
+    let config: FSharpConfig = toml::from_str(input).unwrap();
+    assert_eq!(
+        config.test_framework,
+        FSharpTestFramework::MsTest {
+            version: "3.5.0".into(),
+            adapter_version: "3.5.1".into(),
+        }
+    );
+}
+
+#[test]
+fn fsharp_config_properties_and_item_groups() {
+    let input = r#"
+[properties]
+Nullable = "enable"
+TreatWarningsAsErrors = true
+WarningLevel = 4
+
+[item_groups]
+InternalsVisibleTo = [{ Include = "wibble_test" }]
+Content = [{ Include = "data.json", CopyToOutputDirectory = "PreserveNewest" }]
+"#;
+    let config: FSharpConfig = toml::from_str(input).unwrap();
+    assert_eq!(
+        config.properties,
+        BTreeMap::from([
+            (
+                "Nullable".into(),
+                FSharpPropertyValue::String("enable".into())
+            ),
+            (
+                "TreatWarningsAsErrors".into(),
+                FSharpPropertyValue::Bool(true)
+            ),
+            ("WarningLevel".into(), FSharpPropertyValue::Int(4)),
+        ])
+    );
+    assert_eq!(
+        config.item_groups,
+        BTreeMap::from([
+            (
+                "Content".into(),
+                vec![FSharpItem {
+                    include: "data.json".into(),
+                    attributes: BTreeMap::from([(
+                        "CopyToOutputDirectory".into(),
+                        FSharpPropertyValue::String("PreserveNewest".into())
+                    )]),
+                }]
+            ),
+            (
+                "InternalsVisibleTo".into(),
+                vec![FSharpItem {
+                    include: "wibble_test".into(),
+                    attributes: BTreeMap::new(),
+                }]
+            ),
+        ])
+    );
+}
+
 #[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
 pub enum DenoFlag {
     AllowAll,
@@ -891,6 +1148,19 @@ impl Repository {
         }
     }
 
+    pub fn user(&self) -> Option<&String> {
+        match self {
+            Repository::GitHub { user, .. }
+            | Repository::GitLab { user, .. }
+            | Repository::BitBucket { user, .. }
+            | Repository::Codeberg { user, .. }
+            | Repository::SourceHut { user, .. }
+            | Repository::Gitea { user, .. } => Some(user),
+
+            Repository::Custom { .. } | Repository::None => None,
+        }
+    }
+
     pub fn path(&self) -> Option<&String> {
         match self {
             Repository::GitHub { path, .. }
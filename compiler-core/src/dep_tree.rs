@@ -2,9 +2,6 @@ use ecow::EcoString;
 use petgraph::{algo::Cycle, graph::NodeIndex, Direction};
 use std::collections::{HashMap, HashSet};
 
-#[cfg(test)]
-use pretty_assertions::assert_eq;
-
 /// Take a sequence of values and their deps, and return the values in
 /// order so that deps come before the dependants.
 ///
@@ -91,6 +88,7 @@ pub enum Error {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn toposort_deps_test() {
@@ -207,6 +207,11 @@ file_names.iter().map(|x| x.as_str()).join(", "))]
         error: fsharp::Error,
     },
 
+    #[error("dotnet build failed")]
+    FSharpBuild {
+        errors: Vec<fsharp::msbuild::BuildError>,
+    },
+
     #[error("Invalid runtime for {target} target: {invalid_runtime}")]
     InvalidRuntime {
         target: Target,
@@ -2791,6 +2796,200 @@ implementation but the function name `{function}` is not valid."
                     }
                 }
 
+                TypeError::ExternalTypeWithConstructors { location, name } => {
+                    let text = wrap_format!(
+                        "The type `{name}` has an external F# implementation \
+but it also has constructors. Only types without constructors can be \
+implemented by a .NET type."
+                    );
+                    Diagnostic {
+                        title: "External type with constructors".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
+                TypeError::InvalidExternalFSharpNamespace {
+                    location,
+                    namespace,
+                    name,
+                } => {
+                    let text = wrap_format!(
+                        "The type `{name}` has an external F# \
+implementation but the namespace `{namespace}` is not valid."
+                    );
+                    Diagnostic {
+                        title: "Invalid F# namespace".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
+                TypeError::InvalidExternalFSharpType {
+                    location,
+                    type_name,
+                    name,
+                } => {
+                    let text = wrap_format!(
+                        "The type `{name}` has an external F# \
+implementation but the type name `{type_name}` is not valid."
+                    );
+                    Diagnostic {
+                        title: "Invalid F# type".into(),
+                        text,
+                        hint: Some("A generic .NET type may give its arity after a backtick, \
+for example \"Stack`1\".".into()),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
+                TypeError::ExternalFSharpTypeArity {
+                    location,
+                    name,
+                    expected,
+                    given,
+                } => {
+                    let text = wrap_format!(
+                        "The type `{name}` has {expected} type parameter{} \
+but its external F# implementation has {given}.",
+                        if *expected == 1 { "" } else { "s" }
+                    );
+                    Diagnostic {
+                        title: "Incorrect F# type arity".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
+                TypeError::InvalidExternalFSharpFunction {
+                    location,
+                    function,
+                    name,
+                } => {
+                    let text = wrap_format!(
+                        "The function `{name}` has an external F# \
+implementation but the function name `{function}` is not valid. It may be a \
+curried function `name`, a static method `Name()`, a constructor `new`, a \
+method of the first argument `.Name()` or a property of it `.Name`. Names \
+may be quoted with double backticks, but operators are not supported."
+                    );
+                    Diagnostic {
+                        title: "Invalid F# function".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
+                TypeError::ExternalFSharpConstructorFile {
+                    location,
+                    module,
+                    name,
+                } => {
+                    let text = wrap_format!(
+                        "The function `{name}` has an external F# \
+implementation which is a constructor, but its module `{module}` is a file \
+path. A constructor must be given the name of the .NET type to construct, \
+such as `System.Uri`."
+                    );
+                    Diagnostic {
+                        title: "Invalid F# constructor".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
+                TypeError::ExternalFSharpMemberArity {
+                    location,
+                    function,
+                    name,
+                    arity,
+                } => {
+                    let (member, expected) = if function.ends_with("()") {
+                        ("method", "at least one argument")
+                    } else {
+                        ("property", "exactly one argument")
+                    };
+                    let text = wrap_format!(
+                        "The function `{name}` has an external F# \
+implementation which is the {member} `{function}` of its first argument, so \
+it must take {expected}, but it takes {arity}."
+                    );
+                    Diagnostic {
+                        title: "Incorrect F# member arity".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
                 TypeError::InexhaustiveLetAssignment { location, missing } => {
                     let mut text =wrap(
                         "This assignment uses a pattern that does not \
@@ -3372,6 +3571,21 @@ Fix the warnings and try again."
                         extra_labels: vec![],
                     }),
                 }],
+                fsharp::Error::InvalidBitArraySegment { reason, location } => vec![Diagnostic {
+                    title: "Invalid bit array segment".into(),
+                    text: reason.into(),
+                    hint: None,
+                    level: Level::Error,
+                    location: Some(Location {
+                        label: Label {
+                            text: None,
+                            span: *location,
+                        },
+                        path: path.clone(),
+                        src: src.clone(),
+                        extra_labels: vec![],
+                    }),
+                }],
                 fsharp::Error::Unsupported { feature, location } => vec![Diagnostic {
                     title: "Unsupported feature for compilation target".into(),
                     text: format!("{feature} is not supported for F# compilation."),
@@ -3389,6 +3603,40 @@ Fix the warnings and try again."
                 }],
             },
 
+            Error::FSharpBuild { errors } => errors
+                .iter()
+                .map(|error| match &error.source {
+                    Some((src, span)) => Diagnostic {
+                        title: "F# compilation error".into(),
+                        text: format!(
+                            "The F# generated for this line failed to compile.\n\n{}: {}",
+                            error.code, error.message
+                        ),
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *span,
+                            },
+                            path: error.path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    },
+                    None => Diagnostic {
+                        title: "F# compilation error".into(),
+                        text: format!(
+                            "{}({},{}): {}: {}",
+                            error.path, error.line, error.column, error.code, error.message
+                        ),
+                        hint: None,
+                        level: Level::Error,
+                        location: None,
+                    },
+                })
+                .collect(),
+
             Error::DownloadPackageError {
                 package_name,
                 package_version,
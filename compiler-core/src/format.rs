@@ -763,6 +763,7 @@ impl<'comments> Formatter<'comments> {
             .set_internal(function.publicity)
             .set_external_erlang(&function.external_erlang)
             .set_external_javascript(&function.external_javascript)
+            .set_external_fsharp(&function.external_fsharp)
             .to_doc();
 
         // Fn name and args
@@ -1620,6 +1621,7 @@ impl<'comments> Formatter<'comments> {
         let attributes = AttributesPrinter::new()
             .set_deprecation(&ct.deprecation)
             .set_internal(ct.publicity)
+            .set_external_fsharp(&ct.external_fsharp)
             .to_doc();
 
         let doc = attributes
@@ -3004,6 +3006,7 @@ fn constant_call_arg_formatting<A, B>(
 struct AttributesPrinter<'a> {
     external_erlang: &'a Option<(EcoString, EcoString, SrcSpan)>,
     external_javascript: &'a Option<(EcoString, EcoString, SrcSpan)>,
+    external_fsharp: &'a Option<(EcoString, EcoString, SrcSpan)>,
     deprecation: &'a Deprecation,
     internal: bool,
 }
@@ -3013,6 +3016,7 @@ impl<'a> AttributesPrinter<'a> {
         Self {
             external_erlang: &None,
             external_javascript: &None,
+            external_fsharp: &None,
             deprecation: &Deprecation::NotDeprecated,
             internal: false,
         }
@@ -3034,6 +3038,14 @@ impl<'a> AttributesPrinter<'a> {
         self
     }
 
+    pub fn set_external_fsharp(
+        mut self,
+        external: &'a Option<(EcoString, EcoString, SrcSpan)>,
+    ) -> Self {
+        self.external_fsharp = external;
+        self
+    }
+
     pub fn set_internal(mut self, publicity: Publicity) -> Self {
         self.internal = publicity.is_internal();
         self
@@ -3063,6 +3075,10 @@ impl<'a> Documentable<'a> for AttributesPrinter<'a> {
             attributes.push(docvec!["@external(javascript, \"", m, "\", \"", f, "\")"])
         };
 
+        if let Some((m, f, _)) = self.external_fsharp {
+            attributes.push(docvec!["@external(fsharp, \"", m, "\", \"", f, "\")"])
+        };
+
         // @internal attribute
         if self.internal {
             attributes.push("@internal".to_doc());
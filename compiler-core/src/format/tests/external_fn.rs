@@ -90,3 +90,13 @@ pub fn register(
 "#
     );
 }
+
+#[test]
+fn fsharp_external() {
+    assert_format!(
+        r#"@external(erlang, "one", "one")
+@external(fsharp, "One", "one")
+pub fn one(x: Int) -> Int
+"#
+    );
+}
@@ -29,3 +29,12 @@ fn example5() {
 fn example6() {
     assert_format!("pub type Box(a, b, zero)\n");
 }
+
+#[test]
+fn fsharp_external() {
+    assert_format!(
+        r#"@external(fsharp, "System.Collections.Generic", "Stack")
+pub type Stack(a)
+"#
+    );
+}
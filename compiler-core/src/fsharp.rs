@@ -1,22 +1,28 @@
+mod comparison;
+pub mod msbuild;
+pub mod project_file;
 #[cfg(test)]
 mod tests;
 
-use super::build::Module;
+use super::build::{Module, Origin};
 use super::Result;
 use crate::{
     analyse::Inferred,
     ast::*,
-    config::{FSharpConfig, FSharpOutputType, FSharpTestFramework},
+    config::{FSharpConfig, FSharpIntRepresentation, FSharpOutputType, FSharpTestFramework},
     docvec,
     pretty::*,
     type_::{
         printer::Printer, Deprecation, FieldMap, ModuleValueConstructor, PatternConstructor, Type,
-        TypeVar, TypedCallArg, ValueConstructor, ValueConstructorVariant,
+        TypeVar, TypedCallArg, ValueConstructor, ValueConstructorVariant, PRELUDE_MODULE_NAME,
     },
 };
 use camino::Utf8PathBuf;
+use comparison::ComparisonConstraints;
 use ecow::EcoString;
+use heck::ToUpperCamelCase;
 use itertools::Itertools;
+use num_bigint::BigInt;
 use regex::{Captures, Regex};
 use std::{
     collections::{HashMap, HashSet},
@@ -28,8 +34,12 @@ use vec1::Vec1;
 
 const INDENT: isize = 4;
 pub const FSHARP_PRELUDE: &str = include_str!("./fsharp/prelude.fs");
+/// The name of the project the prelude is compiled into
+pub const PRELUDE_PROJECT_NAME: &str = "gleam_prelude";
 /// This is used directly in pattern matching
 pub const STRING_PATTERN_PARTS: &str = "Gleam_codegen_string_parts";
+/// Used to match on `BigInteger` values, which F# does not allow as literal patterns
+pub const BIGINT_PATTERN: &str = "Gleam_codegen_bigint";
 
 pub const INCOMPLETE_PATTERN_MATCH: &str =
     "#nowarn \"25\" // Incomplete pattern matches on this expression.";
@@ -48,18 +58,60 @@ enum Context {
     Expression,
     Assignment, //?
     Binding,
+    /// The function of a call, which is applied to its arguments directly
+    Callee,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Position {
+    Tail,
+    NotTail,
+}
+
+impl Position {
+    fn is_tail(&self) -> bool {
+        matches!(self, Self::Tail)
+    }
 }
 
 #[derive(Debug)]
 pub struct Generator<'a> {
     package_name: &'a EcoString,
-    pub external_files: HashSet<Utf8PathBuf>,
+    /// External F# files referenced by the rendered modules, along with
+    /// whether they are only referenced from test modules
+    pub external_files: HashMap<Utf8PathBuf, Origin>,
     config: &'a FSharpConfig,
     module: &'a Module,
     input_file_path: &'a Utf8PathBuf,
     printer: Printer<'a>,
     pub suppressed_warnings: HashSet<&'static str>,
     context: Vec<Context>,
+    /// The name of the top level function being generated, used in the
+    /// exceptions raised by `panic`, `todo` and `let assert`
+    current_function: &'a str,
+    /// The arguments of the top level function being generated, which are
+    /// reassigned by self-recursive tail calls
+    current_function_arguments: &'a [TypedArg],
+    /// Whether the expression being generated is in tail position in the
+    /// current top level function
+    function_position: Position,
+    /// We track whether tail call recursion is used so that we can render a
+    /// loop at the top level of the function to use in place of pushing new
+    /// stack frames.
+    tail_recursion_used: bool,
+    /// The type parameters of the top level function being generated. Any
+    /// other type variables in its body are left for F# to infer.
+    function_type_parameters: Option<HashSet<EcoString>>,
+    /// The type parameters of the module's types and functions which need to
+    /// support comparison
+    comparison_constraints: ComparisonConstraints,
+    /// The variables bound so far in the current top level function. A
+    /// variable shadows any module with the same name, so references to such
+    /// modules are qualified with `global`.
+    local_variables: HashSet<EcoString>,
+    /// Whether to emit `#line` directives before each definition and
+    /// statement, so that the F# compiler reports errors at the Gleam source
+    pub line_directives: bool,
 }
 
 impl<'a> Generator<'a> {
@@ -71,13 +123,21 @@ impl<'a> Generator<'a> {
     ) -> Self {
         Self {
             package_name,
-            external_files: HashSet::new(),
+            external_files: HashMap::new(),
             config,
             module,
             input_file_path,
             printer: Printer::new(&module.ast.names),
             suppressed_warnings: HashSet::new(),
             context: Vec::new(),
+            current_function: "",
+            current_function_arguments: &[],
+            function_position: Position::NotTail,
+            tail_recursion_used: false,
+            function_type_parameters: None,
+            comparison_constraints: ComparisonConstraints::new(&module.ast),
+            local_variables: HashSet::new(),
+            line_directives: false,
         }
     }
 
@@ -118,6 +178,7 @@ impl<'a> Generator<'a> {
     ) -> Result<String> {
         self.module = new_module;
         self.printer = Printer::new(&new_module.ast.names);
+        self.comparison_constraints = ComparisonConstraints::new(&new_module.ast);
         self.input_file_path = input_file_path;
         self.render()
     }
@@ -160,15 +221,22 @@ impl<'a> Generator<'a> {
         let mut results = vec![];
 
         for def in self.module.ast.definitions.iter() {
-            match def {
-                Definition::CustomType(t) => results.push(self.custom_type(t)),
-                Definition::TypeAlias(t) => results.push(self.type_alias(t)),
-                Definition::ModuleConstant(c) => results.push(self.module_constant(c)?),
-                Definition::Function(f) => {
-                    results.push(self.function(f)?);
-                }
-                Definition::Import(_) => (), // handled before this function to ensure order
-            }
+            let doc = match def {
+                Definition::CustomType(t) => self.custom_type(t),
+                Definition::TypeAlias(t) => self.type_alias(t),
+                Definition::ModuleConstant(c) => self.module_constant(c)?,
+                Definition::Function(f) => self.function(f)?,
+                Definition::Import(_) => continue, // handled before this function to ensure order
+            };
+            results.push(self.with_line_directive(def.location(), doc));
+        }
+
+        if let Some(class) = self.mstest_class() {
+            results.push(class);
+        }
+
+        if let Some(class) = self.csharp_interop_class() {
+            results.push(docvec![line(), class]);
         }
 
         _ = self.context.pop();
@@ -215,18 +283,22 @@ impl<'a> Generator<'a> {
             }
         };
 
-        unqualified_values.iter().for_each(|v| {
-            let name = &v.name;
-            let label = v.as_name.as_ref().unwrap_or(name);
-            other_aliases.push(docvec![
-                "let ",
-                self.sanitize_name(label),
-                " = ",
-                &full_module_name,
-                ".",
-                self.sanitize_name(name)
-            ]);
-        });
+        // Constructors are referenced with their module instead
+        unqualified_values
+            .iter()
+            .filter(|v| !is_constructor_name(&v.name))
+            .for_each(|v| {
+                let name = &v.name;
+                let label = v.as_name.as_ref().unwrap_or(name);
+                other_aliases.push(docvec![
+                    "let ",
+                    self.sanitize_name(label),
+                    " = ",
+                    &full_module_name,
+                    ".",
+                    self.sanitize_name(name)
+                ]);
+            });
 
         unqualified_types.iter().for_each(|v| {
             let name = &v.name;
@@ -341,30 +413,48 @@ impl<'a> Generator<'a> {
         is_stdlib_package(self.package_name.as_str())
     }
 
-    fn external_type(&self, _type_: &'a CustomType<Arc<Type>>) -> Document<'a> {
-        let params = _type_
-            .parameters
-            .iter()
-            .map(|(_, p)| sanitize_type_var(p).to_doc())
-            .collect::<Vec<_>>();
+    /// Types with an `@external(fsharp, ...)` attribute are aliases of the
+    /// given .NET type, with the parameters of the Gleam type as its generic
+    /// arguments.
+    ///
+    /// ```fsharp
+    /// type Stack<'a> = System.Collections.Generic.Stack<'a>
+    /// ```
+    fn external_type(&self, type_: &'a CustomType<Arc<Type>>) -> Document<'a> {
+        let Some((namespace, type_name, _)) = &type_.external_fsharp else {
+            return nil();
+        };
 
-        let type_name = _type_.name.to_string();
+        // The arity of a generic type is checked during analysis
+        let type_name = match type_name.split_once('`') {
+            Some((name, _arity)) => name,
+            None => type_name.as_str(),
+        };
 
-        let mapped_type = self.config.type_mappings.get(&type_name);
-        if let Some(mapped_type_name) = mapped_type {
-            if params.is_empty() {
-                return docvec!["type ", &_type_.name, " = ", mapped_type_name.to_doc()];
-            }
+        let params = if type_.parameters.is_empty() {
+            nil()
+        } else {
+            join(
+                type_
+                    .parameters
+                    .iter()
+                    .map(|(_, p)| sanitize_type_var(p).to_doc()),
+                ", ".to_doc(),
+            )
+            .surround("<", ">")
+        };
 
-            return docvec![
-                "type ",
-                &_type_.name,
-                join(params, ", ".to_doc()).surround("<", ">"),
-                " = ",
-                mapped_type_name.to_doc()
-            ];
-        }
-        return nil();
+        docvec![
+            self.documentation(&type_.documentation),
+            "type ",
+            &type_.name,
+            params.clone(),
+            " = ",
+            namespace,
+            ".",
+            type_name,
+            params
+        ]
     }
 
     fn record_type(&mut self, type_: &'a CustomType<Arc<Type>>) -> Document<'a> {
@@ -374,12 +464,20 @@ impl<'a> Generator<'a> {
             .expect("Single constructor should exist");
 
         let name = &type_.name;
+        let compiled_names = self.config.csharp_interop && type_.publicity.is_public();
         let fields = constructor
             .arguments
             .iter()
             .map(|r| {
                 let type_doc = self.type_to_fsharp(r.type_.clone());
                 match &r.label {
+                    Some((_, ref label)) if compiled_names => docvec![
+                        self.csharp_compiled_name(label),
+                        " ",
+                        self.sanitize_name(label),
+                        ": ",
+                        type_doc
+                    ],
                     Some((_, ref label)) => docvec![self.sanitize_name(label), ": ", type_doc],
                     None => type_doc,
                 }
@@ -400,7 +498,7 @@ impl<'a> Generator<'a> {
             "type ",
             self.map_publicity(&type_.publicity),
             name,
-            self.type_params(&type_.typed_parameters),
+            self.type_params(name, &type_.typed_parameters),
             " = ",
             opacity,
             join(fields, "; ".to_doc())
@@ -410,18 +508,32 @@ impl<'a> Generator<'a> {
         ]
     }
 
-    fn type_params(&mut self, parameter_types: &[Arc<Type>]) -> Document<'a> {
-        if !parameter_types.is_empty() {
-            join(
-                parameter_types
-                    .iter()
-                    .map(|tp| self.type_to_fsharp(tp.clone())),
-                ", ".to_doc(),
-            )
-            .surround("<", ">")
-        } else {
-            nil()
+    fn type_params(&mut self, type_name: &str, parameter_types: &[Arc<Type>]) -> Document<'a> {
+        if parameter_types.is_empty() {
+            return nil();
         }
+        let comparison_parameters = self
+            .comparison_constraints
+            .type_parameters(type_name)
+            .iter()
+            .filter_map(|index| parameter_types.get(*index).cloned())
+            .collect_vec();
+        let comparison_parameters = comparison_parameters
+            .into_iter()
+            .map(|tp| self.type_to_fsharp(tp))
+            .collect_vec();
+        let parameters = join(
+            parameter_types
+                .iter()
+                .map(|tp| self.type_to_fsharp(tp.clone())),
+            ", ".to_doc(),
+        );
+        docvec![
+            "<",
+            parameters,
+            comparison_constraints(comparison_parameters),
+            ">"
+        ]
     }
 
     fn discriminated_union_type(&mut self, t: &'a CustomType<Arc<Type>>) -> Document<'a> {
@@ -525,7 +637,15 @@ impl<'a> Generator<'a> {
                             })
                             .collect::<Vec<_>>();
 
-                        return Some(
+                        let compiled_name = if self.config.csharp_interop && t.publicity.is_public()
+                        {
+                            docvec![self.csharp_compiled_name(label), line()]
+                        } else {
+                            nil()
+                        };
+
+                        return Some(docvec![
+                            compiled_name,
                             docvec![
                                 "member this.",
                                 label,
@@ -536,8 +656,8 @@ impl<'a> Generator<'a> {
                                 join(cases, line()),
                             ]
                             .group()
-                            .nest(INDENT),
-                        );
+                            .nest(INDENT)
+                        ]);
                     }
                 }
                 None
@@ -560,7 +680,7 @@ impl<'a> Generator<'a> {
             "type ",
             self.map_publicity(&t.publicity),
             type_name,
-            self.type_params(&t.typed_parameters),
+            self.type_params(type_name, &t.typed_parameters),
             " =",
             opacity,
             join(
@@ -612,7 +732,7 @@ impl<'a> Generator<'a> {
             documentation,
             deprecation,
             parameters,
-            type_ast,
+            type_,
             ..
         } = t;
 
@@ -636,63 +756,10 @@ impl<'a> Generator<'a> {
             alias.clone(),
             type_params,
             " = ",
-            self.type_ast_to_fsharp(type_ast)
+            self.type_to_fsharp(type_.clone())
         ]
     }
 
-    /// Needed in addition to type_to_fsharp because type aliases have a different AST structure
-    fn type_ast_to_fsharp(&self, ast: &TypeAst) -> Document<'a> {
-        match ast {
-            TypeAst::Constructor(TypeAstConstructor {
-                name, arguments, ..
-            }) => {
-                let name = map_builtin_type_name_to_fsharp(name);
-                if arguments.is_empty() {
-                    name.to_doc()
-                } else {
-                    docvec![
-                        name,
-                        join(
-                            arguments.iter().map(|arg| self.type_ast_to_fsharp(arg)),
-                            ", ".to_doc()
-                        )
-                        .surround("<", ">")
-                    ]
-                }
-            }
-            TypeAst::Fn(TypeAstFn {
-                arguments, return_, ..
-            }) => {
-                let arg_types = arguments
-                    .iter()
-                    .map(|arg| self.type_ast_to_fsharp(arg))
-                    .collect::<Vec<Document<'a>>>();
-
-                let arg_types = if arg_types.is_empty() {
-                    "unit".to_doc()
-                } else {
-                    join(arg_types, " -> ".to_doc())
-                };
-
-                let return_type = self.type_ast_to_fsharp(return_);
-                docvec![arg_types, " -> ", return_type]
-            }
-            TypeAst::Var(TypeAstVar { name, .. }) => sanitize_type_var(name).to_doc(),
-            TypeAst::Tuple(TypeAstTuple { elems, .. }) => {
-                let items = join(
-                    elems.iter().map(|arg| self.type_ast_to_fsharp(arg)),
-                    " * ".to_doc(),
-                );
-                if elems.len() == 1 {
-                    items
-                } else {
-                    items.surround("(", ")")
-                }
-            }
-            TypeAst::Hole(TypeAstHole { name, .. }) => self.sanitize_name(name),
-        }
-    }
-
     fn function(&mut self, f: &'a TypedFunction) -> Result<Document<'a>> {
         self.context.push(Context::Function);
         let Function {
@@ -700,51 +767,61 @@ impl<'a> Generator<'a> {
             arguments,
             body,
             return_type,
-            return_annotation,
             documentation,
             deprecation,
             external_fsharp,
             ..
         } = f;
         let name_str = name.as_ref().map(|n| n.1.as_str()).unwrap_or("_");
+        self.current_function = name_str;
+        self.local_variables = arguments
+            .iter()
+            .filter_map(|arg| arg.get_variable_name().cloned())
+            .collect();
 
         let sanitized_name = self.sanitize_str(name_str);
 
+        // Functions are always given their generalised signature, so that F#
+        // doesn't narrow them to the types they are used with in the module
+        let (type_parameters, comparison_type_parameters) = if self.is_entry_point_function(f) {
+            (vec![], Some(vec![]))
+        } else {
+            let type_parameters = self.signature_type_variables(arguments, return_type);
+            self.function_type_parameters = Some(type_parameters.iter().cloned().collect());
+            let comparison_type_parameters = self.comparison_type_variables(name_str);
+            (type_parameters, comparison_type_parameters)
+        };
+
         let body: Document<'a> = match external_fsharp {
             Some((ref module_name, ref fn_name, _)) => {
-                // TODO: look into tracking references to the external function in Call expressions
-                // and call them directly instead? Or maybe add an inline specifier for this [instead]?
-
-                let calling_args = if arguments.is_empty() {
-                    "()".to_doc()
-                } else {
-                    join(arguments.iter().map(|arg| self.arg_name(arg)), " ".to_doc())
-                };
-
-                // If the "module" qualifier is a file path, assume that fn_name is fully qualified
-                let qualifier = if module_name.contains("/") || module_name.contains("\\") {
-                    let full_path = self
-                        .input_file_path
-                        .parent()
-                        .expect("must have a parent")
-                        .join(module_name.as_str());
-                    _ =
-                        self.external_files
-                            .insert(full_path.canonicalize_utf8().unwrap_or_else(|_| {
-                                panic!("Failed to canonicalize path: {full_path}")
-                            }));
-                    nil()
-                } else {
-                    docvec![module_name, "."]
-                };
-
-                docvec![qualifier, fn_name, " ", calling_args]
+                self.external_function_call(module_name, fn_name, arguments)
             }
 
             None => {
+                self.current_function_arguments = arguments;
+                self.function_position = Position::Tail;
+                self.tail_recursion_used = false;
                 let statements = self.statements(body, Some(return_type))?;
+                self.function_position = Position::NotTail;
 
-                self.wrap_in_begin_end(statements)
+                let statements = if self.tail_recursion_used {
+                    self.tail_call_loop(statements, arguments)
+                } else {
+                    statements
+                };
+
+                // .NET entry points must return an exit code, so the value
+                // returned by `main` is discarded
+                if self.is_entry_point_function(f) {
+                    self.wrap_in_begin_end(docvec![
+                        "ignore ",
+                        self.wrap_in_begin_end(statements),
+                        line(),
+                        "0"
+                    ])
+                } else {
+                    self.wrap_in_begin_end(statements)
+                }
             }
         };
 
@@ -771,41 +848,15 @@ impl<'a> Generator<'a> {
         };
         let attributes = self.function_attributes(f);
 
-        let mut all_type_params = arguments
-            .iter()
-            .flat_map(|arg| self.get_all_type_variables(arg.type_.clone()))
-            .collect::<HashSet<_>>();
-
-        let return_type = match return_annotation {
-            Some(return_annotation) => self.type_ast_to_fsharp(return_annotation),
-            _ => {
-                let all_return_type_vars = self.get_all_type_variables(return_type.clone());
-                all_type_params.extend(all_return_type_vars);
-                self.type_to_fsharp(return_type.clone())
-            }
-        };
-
-        // HACK: Omitting type params is usually helpful because it can infer type constraints
-        // But some funcitons need to be marked as explicitly generic to avoid getting prematurely narrowed
-        let always_include_type_params = self.is_building_stdlib() && name_str == "map_errors";
-
-        let all_type_params = if always_include_type_params {
-            join(
-                all_type_params.iter().map(Documentable::to_doc),
-                ", ".to_doc(),
-            )
-            .surround("<", ">")
-        } else {
-            nil()
-        };
-
-        let return_type = if self.is_entry_point_function(f) {
-            ": int".to_doc()
-        } else if return_annotation.is_some() {
-            docvec![": ", return_type]
+        let (type_params, return_type) = if self.is_entry_point_function(f) {
+            (nil(), ": int".to_doc())
         } else {
-            nil()
+            (
+                self.function_type_params(&type_parameters, comparison_type_parameters.as_deref()),
+                docvec![": ", self.type_to_fsharp(return_type.clone())],
+            )
         };
+        self.function_type_parameters = None;
 
         // For now, since we mark all modules as recursive, we don't need to mark
         // functions as recursive.
@@ -816,7 +867,7 @@ impl<'a> Generator<'a> {
             "let ",
             self.map_publicity(&f.publicity),
             sanitized_name,
-            all_type_params,
+            type_params,
             " ",
             args,
             return_type,
@@ -827,34 +878,410 @@ impl<'a> Generator<'a> {
         Ok(result)
     }
 
+    /// The body of an `@external(fsharp, ...)` function, calling the external
+    /// with the convention given by the form of its name
+    fn external_function_call(
+        &mut self,
+        module_name: &'a EcoString,
+        fn_name: &'a EcoString,
+        arguments: &'a [TypedArg],
+    ) -> Document<'a> {
+        // TODO: look into tracking references to the external function in Call expressions
+        // and call them directly instead? Or maybe add an inline specifier for this [instead]?
+
+        let (convention, name) = FSharpCallConvention::parse(fn_name)
+            .expect("F# externals are validated during analysis");
+
+        match (convention, arguments) {
+            (FSharpCallConvention::Constructor, _) => {
+                docvec!["new ", module_name, self.tupled_arg_names(arguments)]
+            }
+            // Members are called on the receiver, so the module is only used
+            // to include its file in the project
+            (FSharpCallConvention::InstanceMethod, [receiver, arguments @ ..]) => {
+                let _ = self.register_external_file(module_name);
+                docvec![
+                    self.arg_name(receiver),
+                    ".",
+                    name,
+                    self.tupled_arg_names(arguments)
+                ]
+            }
+            (FSharpCallConvention::PropertyGetter, [receiver]) => {
+                let _ = self.register_external_file(module_name);
+                docvec![self.arg_name(receiver), ".", name]
+            }
+            (FSharpCallConvention::InstanceMethod | FSharpCallConvention::PropertyGetter, _) => {
+                panic!("F# external members are given a receiver during analysis")
+            }
+            (FSharpCallConvention::StaticMethod, _) => {
+                docvec![
+                    self.external_qualifier(module_name),
+                    name,
+                    self.tupled_arg_names(arguments)
+                ]
+            }
+            (FSharpCallConvention::Curried, _) => {
+                let calling_args = if arguments.is_empty() {
+                    "()".to_doc()
+                } else {
+                    join(arguments.iter().map(|arg| self.arg_name(arg)), " ".to_doc())
+                };
+                docvec![
+                    self.external_qualifier(module_name),
+                    name,
+                    " ",
+                    calling_args
+                ]
+            }
+        }
+    }
+
+    fn tupled_arg_names(&self, arguments: &'a [TypedArg]) -> Document<'a> {
+        join(
+            arguments.iter().map(|arg| self.arg_name(arg)),
+            ", ".to_doc(),
+        )
+        .surround("(", ")")
+    }
+
+    /// The module an external function is called from. If the "module" is a
+    /// file path, the function name is assumed to be fully qualified.
+    fn external_qualifier(&mut self, module_name: &'a EcoString) -> Document<'a> {
+        if self.register_external_file(module_name) {
+            nil()
+        } else {
+            docvec![module_name, "."]
+        }
+    }
+
+    /// If the module of an external is a file path, relative to the Gleam
+    /// module, the file is added to the files to include in the project.
+    /// Returns whether the module is a file path.
+    fn register_external_file(&mut self, module_name: &str) -> bool {
+        if !is_external_file_path(module_name) {
+            return false;
+        }
+        let full_path = self
+            .input_file_path
+            .parent()
+            .expect("must have a parent")
+            .join(module_name);
+        // A missing file is reported when it is copied into the project
+        let full_path = full_path.canonicalize_utf8().unwrap_or(full_path);
+        let origin = self.external_files.entry(full_path).or_insert(Origin::Test);
+        if !self.module.is_test() {
+            *origin = Origin::Src;
+        }
+        true
+    }
+
+    /// Self-recursive tail calls assign the new arguments and continue the
+    /// loop, as the .NET runtime does not always honour tail calls.
+    ///
+    /// ```fsharp
+    /// let mutable _loop_x = x
+    /// let mutable _gleam_loop_result = Unchecked.defaultof<_>
+    /// let mutable _gleam_loop_continue = true
+    /// while _gleam_loop_continue do
+    ///     _gleam_loop_continue <- false
+    ///     let x = _loop_x
+    ///     _gleam_loop_result <- begin
+    ///         ...
+    ///     end
+    /// _gleam_loop_result
+    /// ```
+    fn tail_call_loop(&self, body: Document<'a>, arguments: &'a [TypedArg]) -> Document<'a> {
+        let names = arguments.iter().flat_map(TypedArg::get_variable_name);
+        let loop_variables = names.clone().map(|name| {
+            docvec![
+                "let mutable _loop_",
+                name,
+                " = ",
+                self.sanitize_name(name),
+                line()
+            ]
+        });
+        let loop_assignments =
+            names.map(|name| docvec!["let ", self.sanitize_name(name), " = _loop_", name, line()]);
+
+        docvec![
+            concat(loop_variables),
+            "let mutable _gleam_loop_result = Unchecked.defaultof<_>",
+            line(),
+            "let mutable _gleam_loop_continue = true",
+            line(),
+            "while _gleam_loop_continue do",
+            docvec![
+                line(),
+                "_gleam_loop_continue <- false",
+                line(),
+                concat(loop_assignments),
+                "_gleam_loop_result <- ",
+                self.wrap_in_begin_end(body),
+            ]
+            .nest(INDENT),
+            line(),
+            "_gleam_loop_result",
+        ]
+    }
+
+    /// Whether the expression is a call of the current top level function in
+    /// tail position, which is compiled to the next iteration of its loop
+    fn is_tail_call(&self, expr: &'a TypedExpr) -> bool {
+        let TypedExpr::Call { fun, args, .. } = expr else {
+            return false;
+        };
+        // Arguments and other local variables with the same name as the
+        // function shadow it, so have a different variant
+        match fun.as_ref() {
+            TypedExpr::Var {
+                constructor:
+                    ValueConstructor {
+                        variant: ValueConstructorVariant::ModuleFn { module, name, .. },
+                        ..
+                    },
+                ..
+            } => {
+                self.function_position.is_tail()
+                    && *module == self.module.name
+                    && name == self.current_function
+                    && args.len() == self.current_function_arguments.len()
+            }
+            _ => false,
+        }
+    }
+
+    fn tail_call(&mut self, args: &'a [TypedCallArg]) -> Result<Document<'a>> {
+        // Record that tail recursion is happening so that we know to render
+        // the loop at the top level of the function.
+        self.tail_recursion_used = true;
+
+        let mut docs = Vec::with_capacity(args.len() + 2);
+        for (arg, argument) in args.iter().zip(self.current_function_arguments) {
+            let value = self.expression(&arg.value)?;
+            // Discarded arguments are still evaluated, as they may have side
+            // effects
+            docs.push(match argument.get_variable_name() {
+                Some(name) => docvec!["_loop_", name, " <- ", value],
+                None => docvec!["ignore (", value, ")"],
+            });
+        }
+        docs.push("_gleam_loop_continue <- true".to_doc());
+        docs.push("Unchecked.defaultof<_>".to_doc());
+        Ok(join(docs, line()).force_break())
+    }
+
     fn is_entry_point_function(&self, f: &'a TypedFunction) -> bool {
         !self.module.is_test()
             && self.config.output_type == FSharpOutputType::Exe
             && f.name.as_ref().map(|n| n.1.as_str()).unwrap_or("_") == "main"
     }
 
-    fn function_attributes(&self, f: &'a TypedFunction) -> Document<'a> {
-        let Function {
-            arguments,
+    fn is_test_function(&self, f: &'a TypedFunction) -> bool {
+        self.module.is_test()
+            && f.publicity == Publicity::Public
+            && f.return_type.is_nil()
+            && f.arguments.is_empty()
+            && f.name
+                .as_ref()
+                .map(|n| n.1.as_str())
+                .unwrap_or("_")
+                .ends_with("_test")
+    }
+
+    /// MSTest requires tests to be instance methods on a `[<TestClass>]`, so
+    /// a class is generated which calls each of the test functions in the
+    /// module.
+    fn mstest_class(&self) -> Option<Document<'a>> {
+        let FSharpTestFramework::MsTest { .. } = self.config.test_framework else {
+            return None;
+        };
+
+        let tests = self
+            .module
+            .ast
+            .definitions
+            .iter()
+            .filter_map(|def| match def {
+                Definition::Function(f) if self.is_test_function(f) => f.name.as_ref(),
+                _ => None,
+            })
+            .map(|(_, name)| {
+                let name = self.sanitize_str(name);
+                docvec![
+                    "[<Microsoft.VisualStudio.TestTools.UnitTesting.TestMethod>]",
+                    line(),
+                    "member _.",
+                    name.clone(),
+                    "() = ",
+                    name,
+                    " ()"
+                ]
+            })
+            .collect_vec();
+
+        if tests.is_empty() {
+            return None;
+        }
+
+        Some(docvec![
+            "[<Microsoft.VisualStudio.TestTools.UnitTesting.TestClass>]",
+            line(),
+            "type Tests() =",
+            docvec![line(), join(tests, line())].nest(INDENT)
+        ])
+    }
+
+    /// With `csharp_interop` enabled, a class exposing the public functions
+    /// of the module as static methods taking tupled arguments, with PascalCase
+    /// compiled names and `IReadOnlyList` in place of F# lists.
+    ///
+    /// Only lists that are themselves an argument or the return value are
+    /// converted. Lists inside tuples, custom types or function types keep
+    /// their F# list type, which C# can still read as an `IEnumerable`.
+    ///
+    /// Each method is given the line directive of the function it wraps so
+    /// that errors in it aren't reported against the last definition of the
+    /// module.
+    fn csharp_interop_class(&mut self) -> Option<Document<'a>> {
+        if !self.config.csharp_interop {
+            return None;
+        }
+
+        let functions = self
+            .module
+            .ast
+            .definitions
+            .iter()
+            .filter_map(|def| match def {
+                Definition::Function(f)
+                    if f.publicity.is_public()
+                        && !self.is_entry_point_function(f)
+                        && !self.is_test_function(f) =>
+                {
+                    Some(f)
+                }
+                _ => None,
+            })
+            .collect_vec();
+
+        if functions.is_empty() {
+            return None;
+        }
+
+        let members = functions
+            .into_iter()
+            .map(|f| {
+                let member = self.csharp_interop_method(f);
+                self.with_line_directive(f.location, member)
+            })
+            .collect_vec();
+
+        Some(docvec![
+            "[<AbstractClass; Sealed>]",
+            line(),
+            "type Interop =",
+            docvec![line(), join(members, line())].nest(INDENT)
+        ])
+    }
+
+    /// The PascalCase name a public function, type member or record field is
+    /// compiled to when `csharp_interop` is enabled, as C# code expects.
+    /// Gleam type and constructor names are already PascalCase so they are
+    /// left as they are.
+    fn csharp_compiled_name(&self, name: &str) -> Document<'a> {
+        docvec![
+            "[<CompiledName(\"",
+            EcoString::from(name.to_upper_camel_case()),
+            "\")>]"
+        ]
+    }
+
+    fn csharp_interop_method(&mut self, f: &'a TypedFunction) -> Document<'a> {
+        let name = f.name.as_ref().map(|n| n.1.as_str()).unwrap_or("_");
+
+        // Discarded arguments still need a name to be passed on
+        let arguments = f
+            .arguments
+            .iter()
+            .enumerate()
+            .map(|(index, arg)| {
+                let name = match arg.get_variable_name() {
+                    Some(name) => self.sanitize_name(name),
+                    None => EcoString::from(format!("arg{index}")).to_doc(),
+                };
+                (name, arg.type_.clone())
+            })
+            .collect_vec();
+
+        let parameters = arguments
+            .iter()
+            .map(|(name, type_)| docvec![name.clone(), ": ", self.csharp_interop_type(type_)])
+            .collect_vec();
+
+        let call_arguments = if arguments.is_empty() {
+            "()".to_doc()
+        } else {
+            join(
+                arguments.iter().map(|(name, type_)| {
+                    if type_.is_list() {
+                        docvec!["(List.ofSeq ", name.clone(), ")"]
+                    } else {
+                        name.clone()
+                    }
+                }),
+                " ".to_doc(),
+            )
+        };
+
+        let return_type = self.csharp_interop_type(&f.return_type);
+        let call = docvec![self.sanitize_str(name), " ", call_arguments];
+        let call = if f.return_type.is_list() {
+            docvec![call, " :> ", return_type.clone()].surround("(", ")")
+        } else {
+            call
+        };
+
+        docvec![
+            self.csharp_compiled_name(name),
+            line(),
+            "static member ",
+            self.sanitize_str(name),
+            join(parameters, ", ".to_doc()).surround("(", ")"),
+            " : ",
             return_type,
-            ..
-        } = f;
+            " = ",
+            call
+        ]
+    }
+
+    /// F# lists are exposed to C# as `IReadOnlyList`, which they implement
+    fn csharp_interop_type(&mut self, type_: &Arc<Type>) -> Document<'a> {
+        match list_element_type(type_) {
+            Some(element) => docvec![
+                "System.Collections.Generic.IReadOnlyList<",
+                self.type_to_fsharp(element),
+                ">"
+            ],
+            None => self.type_to_fsharp(type_.clone()),
+        }
+    }
+
+    fn function_attributes(&self, f: &'a TypedFunction) -> Document<'a> {
+        let Function { arguments, .. } = f;
         let mut attrs = vec![];
-        if self.module.is_test() && f.publicity == Publicity::Public {
+        if self.is_test_function(f) {
             match self.config.test_framework {
                 FSharpTestFramework::XUnit { .. } => {
-                    if return_type.is_nil()
-                        && arguments.is_empty()
-                        && f.publicity == Publicity::Public
-                        && f.name
-                            .as_ref()
-                            .map(|n| n.1.as_str())
-                            .unwrap_or("_")
-                            .ends_with("_test")
-                    {
-                        attrs.push("[<Xunit.Fact>]".to_doc().append(line()))
-                    }
+                    attrs.push("[<Xunit.Fact>]".to_doc().append(line()))
+                }
+                FSharpTestFramework::NUnit { .. } => {
+                    attrs.push("[<NUnit.Framework.Test>]".to_doc().append(line()))
                 }
+                // MSTest only discovers instance methods of classes, these
+                // are added by `mstest_class`
+                FSharpTestFramework::MsTest { .. } => {}
             }
         }
 
@@ -887,17 +1314,12 @@ impl<'a> Generator<'a> {
             join(
                 arguments.iter().map(|arg| {
                     let arg_name = arg.get_variable_name();
+                    if let Some(arg_name) = arg_name {
+                        _ = self.local_variables.insert(arg_name.clone());
+                    }
 
-                    // HACK: this is mainly to work around issues with how the Dynamic module works
-                    // If this causes problems we'll need to either restrict this behavior to there or figure out another way
-                    let arg_types = match (arg_name, arg.type_.deref()) {
-                        (Some(arg_name), Type::Fn { args, retrn }) if arg_name == "constructor" => {
-                            self.function_type(false, args, retrn)
-                        }
-                        _ => self.type_to_fsharp(arg.type_.clone()),
-                    };
-
-                    docvec![self.arg_name(arg), ": ", arg_types].surround("(", ")")
+                    let arg_type = self.type_to_fsharp(arg.type_.clone());
+                    docvec![self.arg_name(arg), ": ", arg_type].surround("(", ")")
                 }),
                 " ".to_doc(),
             )
@@ -926,7 +1348,7 @@ impl<'a> Generator<'a> {
                 "fun",
                 self.fun_args(args),
                 " -> ",
-                self.statement(body.first())?.0
+                self.statement(body.first(), false)?.0
             ]);
         }
 
@@ -941,7 +1363,13 @@ impl<'a> Generator<'a> {
         ])
     }
 
-    fn statement(&mut self, s: &'a TypedStatement) -> Result<(Document<'a>, Option<Document<'a>>)> {
+    /// Renders a statement, and the value it evaluates to if it has to be
+    /// given explicitly when it is the last statement of a block
+    fn statement(
+        &mut self,
+        s: &'a TypedStatement,
+        is_last: bool,
+    ) -> Result<(Document<'a>, Option<Document<'a>>)> {
         let mut last_var = None;
         let statement_doc = match s {
             Statement::Expression(expr) => {
@@ -967,7 +1395,7 @@ impl<'a> Generator<'a> {
                 ..
             }) => {
                 self.context.push(Context::Assignment);
-                if kind == &AssignmentKind::Let {
+                if kind != &AssignmentKind::Generated {
                     self.add_warning_suppression(INCOMPLETE_PATTERN_MATCH);
                 }
 
@@ -984,8 +1412,8 @@ impl<'a> Generator<'a> {
                     }
                 };
 
-                let res = Ok(docvec![
-                    "let (",
+                let pattern = docvec![
+                    "(",
                     STRING_PATTERN_PARTS,
                     " ",
                     self.string(prefix),
@@ -995,24 +1423,36 @@ impl<'a> Generator<'a> {
                         None => "_, ".to_doc(),
                     },
                     suffix_binding_name,
-                    ")) = ",
-                    self.expression(value)?,
-                ]);
+                    "))",
+                ];
+                let value = self.expression(value)?;
+                let res = match kind {
+                    AssignmentKind::Assert { location } => {
+                        Ok(self.let_assert(pattern, value, *location))
+                    }
+                    AssignmentKind::Let | AssignmentKind::Generated => {
+                        Ok(docvec!["let ", pattern, " = ", value])
+                    }
+                };
                 _ = self.context.pop();
                 res
             }
 
             Statement::Assignment(a) => {
-                if a.kind == AssignmentKind::Let {
+                if a.kind != AssignmentKind::Generated {
                     self.add_warning_suppression(INCOMPLETE_PATTERN_MATCH);
                 }
-                let (name, can_use_as_return_value) = self.get_assignment_binding(&a.pattern)?;
-
-                if can_use_as_return_value {
-                    last_var = Some(name.clone());
+                let (name, value) = self.get_assignment_binding(&a.pattern, is_last)?;
+                last_var = Some(value);
+                match a.kind {
+                    AssignmentKind::Assert { location } => {
+                        let value = self.expression(&a.value)?;
+                        Ok(self.let_assert(name, value, location))
+                    }
+                    AssignmentKind::Let | AssignmentKind::Generated => {
+                        self.assignment(name, &a.value)
+                    }
                 }
-                self.assignment(name, &a.value)
-
             }
             Statement::Use(_) => Ok(docvec!["// This should never be emitted, use statements are transformed into function calls"]),
         };
@@ -1020,28 +1460,57 @@ impl<'a> Generator<'a> {
         Ok((statement_doc?, last_var))
     }
 
+    /// The pattern to bind and the expression giving the assigned value, as
+    /// the value of an assignment is returned when it is the last statement.
+    /// When it is, patterns that can't be used as an expression are bound to a
+    /// name with `as`.
     fn get_assignment_binding(
         &mut self,
         pattern: &'a TypedPattern,
-    ) -> Result<(Document<'a>, bool)> {
+        is_last: bool,
+    ) -> Result<(Document<'a>, Document<'a>)> {
         self.context.push(Context::Binding);
-        let name = self.pattern(pattern)?;
+        let doc = self.pattern(pattern)?;
         _ = self.context.pop();
-        // TODO: Need to disallow a case where the matches in a record constructor pattern only include discards
-        Ok((name, true))
-        // match pattern {
-        //     TypedPattern::Int { .. } | TypedPattern::Float { .. } | TypedPattern::String { .. } => {
-        //         (name, true)
-        //     }
-        //     TypedPattern::Variable { .. } => (name, true),
-        //     TypedPattern::Assign { name, .. } => (name.to_doc(), true),
-        //     TypedPattern::Constructor { type_, .. } if type_.is_bool() => (name.to_doc(), true),
-        //     // Need to disallow a case where the matches in a record constructor pattern only include discards
-        //     TypedPattern::Constructor { constructor, .. } => {
-        //         (name, false)
-        //     }
-        //     _ => (name, true),
-        // }
+        match pattern {
+            TypedPattern::Assign { name, .. } => Ok((doc, name.to_doc())),
+            _ if !is_last || Self::is_reconstructible(pattern) => Ok((doc.clone(), doc)),
+            _ => Ok((
+                doc.append(" as _gleam_assigned"),
+                "_gleam_assigned".to_doc(),
+            )),
+        }
+    }
+
+    /// If the rendered pattern is also a valid expression for the value it
+    /// matched
+    fn is_reconstructible(pattern: &'a TypedPattern) -> bool {
+        match pattern {
+            TypedPattern::Int { .. }
+            | TypedPattern::Float { .. }
+            | TypedPattern::String { .. }
+            | TypedPattern::Variable { .. } => true,
+            TypedPattern::Tuple { elems, .. } => elems.iter().all(Self::is_reconstructible),
+            TypedPattern::List { elements, tail, .. } => {
+                elements.iter().all(Self::is_reconstructible)
+                    && tail.as_deref().is_none_or(Self::is_reconstructible)
+            }
+            // Labelled fields may be rendered as a record pattern, which can
+            // leave fields out
+            TypedPattern::Constructor {
+                arguments,
+                spread: None,
+                constructor:
+                    Inferred::Known(PatternConstructor {
+                        field_map: None, ..
+                    }),
+                ..
+            } => arguments
+                .iter()
+                .all(|argument| Self::is_reconstructible(&argument.value)),
+            TypedPattern::Constructor { arguments, .. } => arguments.is_empty(),
+            _ => false,
+        }
     }
 
     fn assignment(&mut self, name: Document<'a>, value: &'a TypedExpr) -> Result<Document<'a>> {
@@ -1098,10 +1567,12 @@ impl<'a> Generator<'a> {
         let mut last_var = None;
         let mut res = vec![];
 
-        for statement in s {
-            let (statement_doc, maybe_last_var) = self.statement(statement)?;
+        for (index, statement) in s.iter().enumerate() {
+            let is_last = index == s.len() - 1;
+            let (statement_doc, maybe_last_var) =
+                self.statement_in_position(statement, is_last, is_last)?;
             last_var = maybe_last_var;
-            res.push(statement_doc);
+            res.push(self.with_line_directive(statement.location(), statement_doc));
         }
 
         // Can't end on an assignment in F# unless it returns Unit
@@ -1122,19 +1593,58 @@ impl<'a> Generator<'a> {
         Ok(join(res, line()))
     }
 
-    fn sanitize_str(&self, value: &'a str) -> EcoString {
-        let mapped = value
-            .split("/")
-            .map(|s| {
-                if is_reserved_word(s) {
-                    format!("``{s}``")
-                } else {
-                    String::from(s)
-                }
-            })
-            .join(".");
+    /// Renders a statement, which is only in tail position if it is the last
+    /// statement of a block in tail position and is not an assignment
+    fn statement_in_position(
+        &mut self,
+        s: &'a TypedStatement,
+        is_last_statement: bool,
+        is_last: bool,
+    ) -> Result<(Document<'a>, Option<Document<'a>>)> {
+        let function_position = self.function_position;
+        if !is_last_statement || !matches!(s, Statement::Expression(_)) {
+            self.function_position = Position::NotTail;
+        }
+        let result = self.statement(s, is_last);
+        self.function_position = function_position;
+        result
+    }
+
+    /// Precedes the document with a `#line` directive for the Gleam line the
+    /// location starts on, if line directives are enabled. The document must
+    /// start on a new line.
+    fn with_line_directive(&self, location: SrcSpan, doc: Document<'a>) -> Document<'a> {
+        if !self.line_directives {
+            return doc;
+        }
+        let line_number = self
+            .module
+            .ast
+            .type_info
+            .line_numbers
+            .line_number(location.start);
+        // Forward slashes are used as backslashes would be read as escapes
+        let path = self.input_file_path.as_str().replace('\\', "/");
+        docvec![
+            EcoString::from(format!("#line {line_number} \"{path}\"")),
+            line(),
+            doc
+        ]
+    }
 
-        EcoString::from(mapped)
+    /// The name of an imported module, qualified with `global` if a local
+    /// variable shadows it
+    fn module_reference(&self, module_name: &EcoString) -> Document<'a> {
+        let first_segment = module_name.split('/').next().unwrap_or_default();
+        if self.local_variables.contains(first_segment) {
+            docvec!["global.", self.sanitize_name(module_name)]
+        } else {
+            self.sanitize_name(module_name)
+        }
+    }
+
+    fn sanitize_str(&self, value: &'a str) -> EcoString {
+        sanitize_module_name(value)
     }
 
     fn sanitize_name(&self, name: &EcoString) -> Document<'a> {
@@ -1153,7 +1663,14 @@ impl<'a> Generator<'a> {
                     // TODO: See if we can emit a warning here because it probably wasn't intentional
                     format!("{slashes}u{{{unicode}}}") // return the original string
                 } else {
-                    format!("{slashes}u{unicode}")
+                    // F# escapes have a fixed number of digits, with `\U`
+                    // for codepoints outside the basic multilingual plane
+                    let codepoint = u32::from_str_radix(unicode, 16).unwrap_or(0);
+                    if codepoint > 0xFFFF {
+                        format!("{slashes}U{codepoint:08X}")
+                    } else {
+                        format!("{slashes}u{codepoint:04X}")
+                    }
                 }
             })
             .to_string();
@@ -1167,14 +1684,60 @@ impl<'a> Generator<'a> {
     /// When running on the Erlang virtual machine ints have no maximum and minimum size.
     /// When running on JavaScript runtimes ints are represented using JavaScript's 64 bit floating point numbers,
     /// For now we'll assume for maximum compatibility that we'll need to use 64 bits for ints
-    fn integer(&self, value: &'a EcoString) -> Document<'a> {
-        value.to_doc().append("L")
+    fn integer(&self, value: &'a EcoString, int_value: &BigInt) -> Document<'a> {
+        match self.config.int_representation {
+            FSharpIntRepresentation::Int64 => value.to_doc().append("L"),
+            // BigInteger literals can only be written in decimal
+            FSharpIntRepresentation::BigInt => EcoString::from(format!("{int_value}I")).to_doc(),
+        }
+    }
+
+    fn int_pattern(&self, value: &'a EcoString, int_value: &BigInt) -> Document<'a> {
+        match self.config.int_representation {
+            FSharpIntRepresentation::Int64 => self.integer(value, int_value),
+            FSharpIntRepresentation::BigInt => {
+                docvec![BIGINT_PATTERN, " ", self.string(&format!("{int_value}"))]
+            }
+        }
     }
 
-    fn expression(&mut self, expr: &'a TypedExpr) -> Result<Document<'a>> {
+    /// Convert an `Int` expression to the `int64` used by the bit array prelude
+    /// functions, using the given prelude function when `Int`s are `bigint`s
+    /// so values out of the `int64` range are handled explicitly.
+    fn int_to_int64(&self, value: Document<'a>, conversion: &'static str) -> Document<'a> {
+        match self.config.int_representation {
+            FSharpIntRepresentation::Int64 => value,
+            FSharpIntRepresentation::BigInt => docvec![conversion, " ", value.surround("(", ")")],
+        }
+    }
+
+    fn expression(&mut self, expr: &'a TypedExpr) -> Result<Document<'a>> {
+        let is_callee = matches!(self.context.last(), Some(Context::Callee));
+        if !is_callee {
+            if let Some((type_, field_map)) = curried_constructor(expr) {
+                return self.curried_constructor(expr, type_, field_map);
+            }
+        }
+
         self.context.push(Context::Expression);
+        let is_tail_call = self.is_tail_call(expr);
+
+        // Only the last statement of a block or pipeline and the clauses of a
+        // case can be in the same position as the expression containing them
+        let function_position = self.function_position;
+        if !matches!(
+            expr,
+            TypedExpr::Block { .. } | TypedExpr::Pipeline { .. } | TypedExpr::Case { .. }
+        ) {
+            self.function_position = Position::NotTail;
+        }
+
         let res = match expr {
-            TypedExpr::Int { value, .. } => Ok(self.integer(value)),
+            TypedExpr::Call { args, .. } if is_tail_call => self.tail_call(args),
+
+            TypedExpr::Int {
+                value, int_value, ..
+            } => Ok(self.integer(value, int_value)),
             TypedExpr::Float { value, .. } => Ok(value.to_doc()),
             TypedExpr::String { value, .. } => Ok(self.string(value.as_str())),
             TypedExpr::Block { statements, .. } => self.block(statements),
@@ -1184,6 +1747,34 @@ impl<'a> Generator<'a> {
                 ..
             } => self.pipeline(assignments, finally),
 
+            // Prelude constructors imported with a different name
+            TypedExpr::Var {
+                name: alias,
+                constructor:
+                    ValueConstructor {
+                        variant: ValueConstructorVariant::Record { name, module, .. },
+                        ..
+                    },
+                ..
+            } if module == PRELUDE_MODULE_NAME && alias != name => {
+                Ok(qualified_prelude_value(name))
+            }
+
+            // Constructors aren't aliased when imported unqualified, as F#
+            // can't alias union cases for use in patterns
+            TypedExpr::Var {
+                constructor:
+                    ValueConstructor {
+                        variant: ValueConstructorVariant::Record { name, module, .. },
+                        ..
+                    },
+                ..
+            } if module != PRELUDE_MODULE_NAME && *module != self.module.name => Ok(docvec![
+                self.module_reference(module),
+                ".",
+                self.sanitize_name(name)
+            ]),
+
             TypedExpr::Var { name, .. } => Ok(match name.as_str() {
                 "Nil" => "()".to_doc(),
                 "True" => "true".to_doc(),
@@ -1191,21 +1782,19 @@ impl<'a> Generator<'a> {
                 _ => self.sanitize_name(name).to_doc(),
             }),
             TypedExpr::Fn { args, body, .. } => self.fun(args, body),
-            TypedExpr::List { elements, tail, .. } => {
-                let list = join(
-                    elements
-                        .iter()
-                        .map(|e| self.expression(e))
-                        .collect_results()?,
-                    "; ".to_doc(),
-                )
-                .surround("[", "]");
-
-                match tail {
-                    Some(tail) => Ok(docvec![list, " @ ", self.expression(tail)?]),
-                    None => Ok(list),
-                }
-            }
+            TypedExpr::List {
+                elements,
+                tail: Some(tail),
+                ..
+            } => self.list_prepend(elements, tail),
+            TypedExpr::List { elements, .. } => Ok(join(
+                elements
+                    .iter()
+                    .map(|e| self.expression(e))
+                    .collect_results()?,
+                "; ".to_doc(),
+            )
+            .surround("[", "]")),
 
             TypedExpr::Call { fun, args, .. } => match fun.as_ref() {
                 TypedExpr::Var {
@@ -1213,22 +1802,33 @@ impl<'a> Generator<'a> {
                         ValueConstructor {
                             variant:
                                 ValueConstructorVariant::Record {
-                                    constructors_count: 1,
+                                    name,
+                                    variants_count,
                                     arity,
                                     field_map: Some(ref field_map),
                                     ..
                                 },
+                            type_,
                             ..
                         },
                     ..
-                } if *arity == field_map.fields.len() as u16 => {
-                    // Every constructor field must have a label to be a record type
-                    self.record_instantiation(field_map, args)
+                } if is_record_constructor(name, type_, *variants_count, *arity, field_map) => {
+                    self.record_instantiation(type_, field_map, args)
                 }
 
-                // HACK: Workaround for constructor issues in Dynamic module
-                TypedExpr::Var { name, .. } if name == "constructor" => {
-                    self.function_call(false, fun, args)
+                TypedExpr::ModuleSelect {
+                    constructor:
+                        ModuleValueConstructor::Record {
+                            name,
+                            variants_count,
+                            arity,
+                            field_map: Some(ref field_map),
+                            type_,
+                            ..
+                        },
+                    ..
+                } if is_record_constructor(name, type_, *variants_count, *arity, field_map) => {
+                    self.record_instantiation(type_, field_map, args)
                 }
 
                 // If it's a module select but just an alias for a record constructor,
@@ -1258,14 +1858,20 @@ impl<'a> Generator<'a> {
             } => self.case(subjects, clauses),
 
             TypedExpr::Tuple { elems, .. } => {
-                let items = elems.iter().map(|e| self.expression(e)).collect_results()?;
+                let items = match elems.as_slice() {
+                    // The element of a 1-tuple is an argument to `Tuple1`
+                    [elem] if !self.must_be_multiline(elem) && self.must_be_parenthesized(elem) => {
+                        vec![self.expression(elem)?.surround("(", ")")]
+                    }
+                    _ => elems.iter().map(|e| self.expression(e)).collect_results()?,
+                };
                 Ok(self.tuple(items))
             }
 
-            // Special case for double negation
             TypedExpr::NegateInt { value, .. } => {
-                // Special case for double negation
-                if let TypedExpr::NegateInt { .. } = value.as_ref() {
+                // Double negations and calls are wrapped so that the `-`
+                // applies to the whole value
+                if let TypedExpr::NegateInt { .. } | TypedExpr::Call { .. } = value.as_ref() {
                     Ok("-"
                         .to_doc()
                         .append(self.expression(value)?.surround("(", ")")))
@@ -1274,19 +1880,39 @@ impl<'a> Generator<'a> {
                 }
             }
 
-            TypedExpr::Todo { message, .. } => self.todo(message),
-            TypedExpr::Panic { message, .. } => self.panic_(message),
+            TypedExpr::Todo {
+                message, location, ..
+            } => self.todo(message, *location),
+            TypedExpr::Panic {
+                message, location, ..
+            } => self.panic_(message, *location),
             TypedExpr::RecordAccess { label, record, .. } => self.record_access(record, label),
+            TypedExpr::RecordUpdate {
+                args,
+                record,
+                constructor,
+                ..
+            } if !is_record_update_of_record_type(constructor) => {
+                self.union_record_update(&record.value, constructor, args)
+            }
+
             TypedExpr::RecordUpdate { args, record, .. } => {
                 // If the target of the update is the result of a pipeline, it needs to be
                 // surrounded in parentheses
-                let old_var_name = match record.deref() {
-                    TypedExpr::Pipeline { .. } => Ok(self.expression(record)?.surround("(", ")")),
-                    _ => self.expression(record),
+                let old_var_name = match record.value.deref() {
+                    TypedExpr::Pipeline { .. } => {
+                        Ok(self.expression(&record.value)?.surround("(", ")"))
+                    }
+                    _ => self.expression(&record.value),
                 };
 
+                // Fields which aren't updated are given as implicit accesses
+                // of the old record, F# copies these itself. The updated
+                // fields are kept in the order they were written.
                 let new_values = args
                     .iter()
+                    .filter(|arg| arg.implicit.is_none())
+                    .sorted_by_key(|arg| arg.location.start)
                     .map(|arg| {
                         let child_expr = match &arg.value {
                             // If the child here is a pipe operation, we need to indent at least
@@ -1307,10 +1933,14 @@ impl<'a> Generator<'a> {
                     " }"
                 ])
             }
+            // The prelude is not a module in F#, its values are used directly
+            TypedExpr::ModuleSelect {
+                module_name, label, ..
+            } if module_name == PRELUDE_MODULE_NAME => Ok(qualified_prelude_value(label)),
             TypedExpr::ModuleSelect {
                 module_name, label, ..
             } => {
-                let full_module_name = self.sanitize_name(module_name);
+                let full_module_name = self.module_reference(module_name);
                 let full_module_name = if full_module_name.is_empty() {
                     self.sanitize_name(label).to_doc()
                 } else {
@@ -1325,6 +1955,7 @@ impl<'a> Generator<'a> {
             TypedExpr::BitArray { segments, .. } => self.bit_array(segments),
             TypedExpr::Invalid { .. } => Ok("// TODO: TypedExpr::Invalid".to_doc()),
         };
+        self.function_position = function_position;
         _ = self.context.pop();
         res
     }
@@ -1372,10 +2003,14 @@ impl<'a> Generator<'a> {
 
         // let mut value_param = "value = failwith \"Invalid value provided\"".to_doc();
         let mut value_param = match kind {
-            BitArraySegmentKind::Int => Ok(docvec![
-                "value = BitArraySegmentValue.Int",
-                expr.to_doc(self)?.surround("(", ")")
-            ]),
+            BitArraySegmentKind::Int => {
+                let value = expr.to_doc(self)?;
+                Ok(docvec![
+                    "value = BitArraySegmentValue.Int",
+                    self.int_to_int64(value, "bigintSegmentValue")
+                        .surround("(", ")")
+                ])
+            }
             BitArraySegmentKind::Float => Ok(docvec![
                 "value = BitArraySegmentValue.Float",
                 expr.to_doc(self)?.surround("(", ")")
@@ -1393,30 +2028,6 @@ impl<'a> Generator<'a> {
                 "value = BitArraySegmentValue.Utf8Codepoint",
                 expr.to_doc(self)?.surround("(", ")")
             ]),
-            BitArraySegmentKind::Utf8 => self.make_error(Error::Unsupported {
-                feature: EcoString::from("BitArraySegmentKind::Utf8"),
-                location: segment.location,
-            }),
-            BitArraySegmentKind::Utf16 => self.make_error(Error::Unsupported {
-                feature: EcoString::from("BitArraySegmentKind::Utf16"),
-                location: segment.location,
-            }),
-            BitArraySegmentKind::Utf32 => self.make_error(Error::Unsupported {
-                feature: EcoString::from("BitArraySegmentKind::Utf32"),
-                location: segment.location,
-            }),
-            BitArraySegmentKind::Utf8Codepoint => self.make_error(Error::Unsupported {
-                feature: EcoString::from("BitArraySegmentKind::Utf8Codepoint"),
-                location: segment.location,
-            }),
-            BitArraySegmentKind::Utf16Codepoint => self.make_error(Error::Unsupported {
-                feature: EcoString::from("BitArraySegmentKind::Utf16Codepoint"),
-                location: segment.location,
-            }),
-            BitArraySegmentKind::Utf32Codepoint => self.make_error(Error::Unsupported {
-                feature: EcoString::from("BitArraySegmentKind::Utf32Codepoint"),
-                location: segment.location,
-            }),
             BitArraySegmentKind::Invalid => self.make_error(Error::Unsupported {
                 feature: EcoString::from("BitArraySegmentKind::Invalid"),
                 location: segment.location,
@@ -1457,42 +2068,59 @@ impl<'a> Generator<'a> {
                     ];
                 }
                 BitArrayOption::Utf8 { .. } => {
-                    if BitArraySegmentKind::String == kind {
-                        value_param = docvec![
-                            "value = BitArraySegmentValue.Utf8(System.Text.Encoding.UTF8.GetBytes",
-                            expr.to_doc(self)?.surround("(", ")"),
-                            ")"
-                        ];
-                    } else {
-                        value_param =
-                            docvec!["value = failwith \"Invalid value provided for Utf8\"",];
-                    }
+                    value_param =
+                        self.bit_array_utf_value("Utf8", expr, &kind, segment.location, |value| {
+                            docvec![
+                                "System.Text.Encoding.UTF8.GetBytes",
+                                value.surround("(", ")")
+                            ]
+                        })?;
                 }
                 BitArrayOption::Utf16 { .. } => {
-                    if BitArraySegmentKind::String == kind {
-                        value_param = docvec![
-                            "value = BitArraySegmentValue.Utf16(System.Text.Encoding.Unicode.GetBytes",
-                            expr.to_doc(self)?.surround("(", ")")
-                        ];
-                    } else {
-                        value_param =
-                            docvec!["value = failwith \"Invalid value provided for Utf16\"",];
-                    }
+                    value_param = self.bit_array_utf_value(
+                        "Utf16",
+                        expr,
+                        &kind,
+                        segment.location,
+                        |value| value,
+                    )?;
                 }
                 BitArrayOption::Utf32 { .. } => {
-                    if BitArraySegmentKind::String == kind {
-                        value_param = docvec![
-                            "value = BitArraySegmentValue.Utf32(System.Text.Encoding.UTF32.GetBytes",
-                            expr.to_doc(self)?.surround("(", ")")
-                        ];
-                    } else {
-                        value_param =
-                            docvec!["value = failwith \"Invalid value provided for Utf32\"",];
-                    }
+                    value_param = self.bit_array_utf_value(
+                        "Utf32",
+                        expr,
+                        &kind,
+                        segment.location,
+                        |value| value,
+                    )?;
+                }
+                BitArrayOption::Utf8Codepoint { .. } => {
+                    value_param = self.bit_array_utf_value(
+                        "Utf8Codepoint",
+                        expr,
+                        &kind,
+                        segment.location,
+                        |value| value,
+                    )?;
+                }
+                BitArrayOption::Utf16Codepoint { .. } => {
+                    value_param = self.bit_array_utf_value(
+                        "Utf16Codepoint",
+                        expr,
+                        &kind,
+                        segment.location,
+                        |value| value,
+                    )?;
+                }
+                BitArrayOption::Utf32Codepoint { .. } => {
+                    value_param = self.bit_array_utf_value(
+                        "Utf32Codepoint",
+                        expr,
+                        &kind,
+                        segment.location,
+                        |value| value,
+                    )?;
                 }
-                BitArrayOption::Utf8Codepoint { .. } => (),
-                BitArrayOption::Utf16Codepoint { .. } => (),
-                BitArrayOption::Utf32Codepoint { .. } => (),
                 BitArrayOption::Signed { .. } => signed_param = docvec!["signed = Some true"],
                 BitArrayOption::Unsigned { .. } => signed_param = docvec!["signed = Some false"],
                 BitArrayOption::Big { .. } => {
@@ -1505,7 +2133,9 @@ impl<'a> Generator<'a> {
                     endianness_param = docvec!["endianness = Some BitArrayEndianness.Native"]
                 }
                 BitArrayOption::Size { value, .. } => {
-                    size_param = docvec!["size = ", value.to_doc(self)?.surround("Some(", ")")]
+                    let value = value.to_doc(self)?;
+                    let value = self.int_to_int64(value, "bigintSegmentSize");
+                    size_param = docvec!["size = ", value.surround("Some(", ")")]
                 }
                 BitArrayOption::Unit { value, .. } => {
                     let value_str = EcoString::from(value.to_string());
@@ -1527,6 +2157,140 @@ impl<'a> Generator<'a> {
         Ok(join(fields, "; ".to_doc()).group().surround("{ ", " }"))
     }
 
+    /// The value of a UTF segment. UTF-16 and UTF-32 values are encoded by
+    /// the prelude, which applies the endianness of the segment.
+    fn bit_array_utf_value<T: ExpressionLike>(
+        &mut self,
+        case: &'static str,
+        expr: &'a T,
+        kind: &BitArraySegmentKind,
+        location: SrcSpan,
+        encode: impl FnOnce(Document<'a>) -> Document<'a>,
+    ) -> Result<Document<'a>> {
+        let expected_kind = if case.ends_with("Codepoint") {
+            BitArraySegmentKind::UtfCodepoint
+        } else {
+            BitArraySegmentKind::String
+        };
+        if *kind != expected_kind {
+            let expected = match expected_kind {
+                BitArraySegmentKind::UtfCodepoint => "UtfCodepoint",
+                _ => "String",
+            };
+            return self.make_error(Error::InvalidBitArraySegment {
+                reason: EcoString::from(format!(
+                    "A {case} segment must be given a {expected} value."
+                )),
+                location,
+            });
+        }
+        let value = expr.to_doc(self)?;
+        Ok(docvec![
+            "value = BitArraySegmentValue.",
+            case,
+            encode(value).surround("(", ")")
+        ])
+    }
+
+    /// Types rendered as F# unions don't have a copy and update expression,
+    /// so the old value is destructured to construct the new one.
+    ///
+    /// ```fsharp
+    /// begin
+    ///     match old with
+    ///     | Wibble(_gleam_field_0, _) -> Wibble(_gleam_field_0, new_value)
+    /// end
+    /// ```
+    fn union_record_update(
+        &mut self,
+        old: &'a TypedExpr,
+        constructor: &'a TypedExpr,
+        args: &'a [TypedCallArg],
+    ) -> Result<Document<'a>> {
+        // The record may be a different variant when updating a type with
+        // several constructors
+        self.add_warning_suppression(INCOMPLETE_PATTERN_MATCH);
+        // The constructor is applied directly, in both the pattern and the
+        // new value, rather than being wrapped as a function value
+        self.context.push(Context::Callee);
+        let constructor = self.expression(constructor);
+        _ = self.context.pop();
+        let constructor = constructor?;
+        let mut patterns = Vec::with_capacity(args.len());
+        let mut values = Vec::with_capacity(args.len());
+        for (index, arg) in args.iter().enumerate() {
+            if arg.implicit.is_some() {
+                let name = EcoString::from(format!("_gleam_field_{index}"));
+                patterns.push(name.clone().to_doc());
+                values.push(name.to_doc());
+            } else if self.must_be_parenthesized_arg(arg) {
+                patterns.push("_".to_doc());
+                values.push(self.expression(&arg.value)?.surround("(", ")"));
+            } else {
+                patterns.push("_".to_doc());
+                values.push(self.expression(&arg.value)?);
+            }
+        }
+
+        let old = match old {
+            TypedExpr::Pipeline { .. } => self.expression(old)?.surround("(", ")"),
+            _ => self.expression(old)?,
+        };
+
+        Ok(self.wrap_in_begin_end(
+            docvec![
+                "match ",
+                old,
+                " with",
+                line(),
+                "| ",
+                constructor.clone(),
+                join(patterns, ", ".to_doc()).surround("(", ")"),
+                " -> ",
+                constructor,
+                join(values, ", ".to_doc()).surround("(", ")"),
+            ]
+            .force_break(),
+        ))
+    }
+
+    /// The elements are prepended to the tail with `::`, rather than being
+    /// appended as a new list, so that the tail isn't copied
+    fn list_prepend(
+        &mut self,
+        elements: &'a [TypedExpr],
+        tail: &'a TypedExpr,
+    ) -> Result<Document<'a>> {
+        let mut items = elements
+            .iter()
+            .map(|element| self.list_prepend_operand(element))
+            .collect_results()?;
+        items.push(self.list_prepend_operand(tail)?);
+        Ok(join(items, " :: ".to_doc()).group())
+    }
+
+    /// Operands of `::` are wrapped in parentheses unless they bind more
+    /// tightly than it
+    fn list_prepend_operand(&mut self, expr: &'a TypedExpr) -> Result<Document<'a>> {
+        match expr {
+            TypedExpr::Call { .. } if self.must_be_multiline(expr) => {
+                Ok(self.expression(expr)?.surround("(", ")"))
+            }
+            TypedExpr::Var { .. }
+            | TypedExpr::ModuleSelect { .. }
+            | TypedExpr::Int { .. }
+            | TypedExpr::Float { .. }
+            | TypedExpr::String { .. }
+            | TypedExpr::Tuple { .. }
+            | TypedExpr::TupleIndex { .. }
+            | TypedExpr::NegateInt { .. }
+            | TypedExpr::RecordAccess { .. }
+            | TypedExpr::Call { .. }
+            | TypedExpr::List { tail: None, .. } => self.expression(expr),
+            _ => Ok(self.expression(expr)?.surround("(", ")")),
+        }
+    }
+
     fn tuple_index(&mut self, tuple: &'a TypedExpr, index: &'a u64) -> Result<Document<'a>> {
         self.add_warning_suppression(CONSTRUCT_NOT_USUALLY_USED_FROM_FSHARP);
         Ok(docvec![self.expression(tuple)?, ".Item", index + 1])
@@ -1534,25 +2298,51 @@ impl<'a> Generator<'a> {
 
     fn record_instantiation(
         &mut self,
+        constructor_type: &'a Type,
         field_map: &'a FieldMap,
         args: &'a [CallArg<TypedExpr>],
     ) -> Result<Document<'a>> {
-        let field_map = invert_field_map(field_map);
-
-        let args = args
+        let values = args
             .iter()
-            .enumerate()
-            .map(|(i, arg)| {
-                let label = field_map.get(&(i as u32)).expect("Index out of bounds");
-                Ok(docvec![
-                    self.sanitize_name(label).to_doc(),
-                    " = ",
-                    self.expression(&arg.value)?
-                ])
-            })
+            .map(|arg| self.expression(&arg.value))
             .collect_results()?;
+        Ok(self.record_expression(constructor_type, field_map, values))
+    }
+
+    fn record_expression(
+        &self,
+        constructor_type: &'a Type,
+        field_map: &'a FieldMap,
+        values: Vec<Document<'a>>,
+    ) -> Document<'a> {
+        let field_map = invert_field_map(field_map);
+        let record_type = constructor_type
+            .return_type()
+            .unwrap_or_else(|| Arc::new(constructor_type.clone()));
+
+        let fields = values.into_iter().enumerate().map(|(i, value)| {
+            let label = field_map.get(&(i as u32)).expect("Index out of bounds");
+            let qualifier = if i == 0 {
+                self.record_label_qualifier(&record_type)
+            } else {
+                nil()
+            };
+            docvec![qualifier, self.sanitize_name(label).to_doc(), " = ", value]
+        });
 
-        Ok(join(args, "; ".to_doc()).group().surround("{ ", " }"))
+        join(fields, "; ".to_doc()).group().surround("{ ", " }")
+    }
+
+    /// The labels of records from other modules are not in scope, so the
+    /// first label of a record expression or pattern is qualified with the
+    /// module and type
+    fn record_label_qualifier(&self, record_type: &Type) -> Document<'a> {
+        match record_type.named_type_name() {
+            Some((module, name)) if module != self.module.name => {
+                docvec![self.module_reference(&module), ".", name, "."]
+            }
+            _ => nil(),
+        }
     }
 
     // If an expression is one of these types, it must take up multiple lines, regardless of how long it is
@@ -1624,14 +2414,59 @@ impl<'a> Generator<'a> {
             }
         };
         // If for some reason we're doing an IIFE, we need to wrap it in parens
+        self.context.push(Context::Callee);
         let fun_expr = if matches!(fun, TypedExpr::Fn { .. }) {
-            Ok(self.expression(fun)?.surround("(", ")"))
+            self.expression(fun).map(|fun| fun.surround("(", ")"))
         } else {
             self.expression(fun)
         };
+        _ = self.context.pop();
         Ok(fun_expr?.append(args).group())
     }
 
+    /// Union cases with several fields take them as a tuple, and records are
+    /// built with a record expression, so when these constructors are used as
+    /// values they are wrapped in a curried function to match their Gleam type
+    fn curried_constructor(
+        &mut self,
+        constructor: &'a TypedExpr,
+        type_: &'a Type,
+        field_map: Option<&'a FieldMap>,
+    ) -> Result<Document<'a>> {
+        let arity = type_.fn_arity().unwrap_or(0);
+        let names = (1..=arity)
+            .map(|i| EcoString::from(format!("_arg{i}")))
+            .collect_vec();
+
+        let value = match field_map {
+            Some(field_map) => {
+                let values = names.iter().map(|name| name.clone().to_doc()).collect_vec();
+                self.record_expression(type_, field_map, values)
+            }
+            None => {
+                self.context.push(Context::Callee);
+                let constructor = self.expression(constructor);
+                _ = self.context.pop();
+                docvec![
+                    constructor?,
+                    join(
+                        names.iter().map(|name| name.clone().to_doc()),
+                        ", ".to_doc()
+                    )
+                    .surround("(", ")")
+                ]
+            }
+        };
+
+        Ok(docvec![
+            "(fun ",
+            join(names.into_iter().map(|name| name.to_doc()), " ".to_doc()),
+            " -> ",
+            value,
+            ")"
+        ])
+    }
+
     fn record_access(
         &mut self,
         record: &'a TypedExpr,
@@ -1648,11 +2483,15 @@ impl<'a> Generator<'a> {
 
     /// Not all function arguments need to have parentheses
     /// We can omit them in many cases when they are simple values
-    fn must_be_parenthesized_arg(&self, expr: &'a TypedCallArg) -> bool {
-        if self.must_be_multiline(&expr.value) {
+    fn must_be_parenthesized_arg(&self, arg: &'a TypedCallArg) -> bool {
+        self.must_be_parenthesized(&arg.value)
+    }
+
+    fn must_be_parenthesized(&self, expr: &'a TypedExpr) -> bool {
+        if self.must_be_multiline(expr) {
             return true;
         }
-        match expr.value {
+        match expr {
             TypedExpr::Var { .. }
             | TypedExpr::ModuleSelect { .. }
             | TypedExpr::Int { .. }
@@ -1661,7 +2500,6 @@ impl<'a> Generator<'a> {
             | TypedExpr::Fn { .. }
             | TypedExpr::Tuple { .. }
             | TypedExpr::TupleIndex { .. }
-            | TypedExpr::NegateBool { .. }
             | TypedExpr::NegateInt { .. }
             | TypedExpr::RecordAccess { .. } => false,
 
@@ -1669,22 +2507,111 @@ impl<'a> Generator<'a> {
         }
     }
 
-    fn todo(&mut self, message: &'a Option<Box<TypedExpr>>) -> Result<Document<'a>> {
+    fn todo(
+        &mut self,
+        message: &'a Option<Box<TypedExpr>>,
+        location: SrcSpan,
+    ) -> Result<Document<'a>> {
+        let message = match message {
+            Some(message) => self.gleam_error_message(message)?,
+            None => {
+                self.string("`todo` expression evaluated. This code has not yet been implemented.")
+            }
+        };
+        Ok(self.raise_gleam_error("GleamTodo", message, location, None))
+    }
+
+    fn panic_(
+        &mut self,
+        message: &'a Option<Box<TypedExpr>>,
+        location: SrcSpan,
+    ) -> Result<Document<'a>> {
+        let message = match message {
+            Some(message) => self.gleam_error_message(message)?,
+            None => self.string("`panic` expression evaluated."),
+        };
+        Ok(self.raise_gleam_error("GleamPanic", message, location, None))
+    }
+
+    fn gleam_error_message(&mut self, message: &'a TypedExpr) -> Result<Document<'a>> {
+        let doc = self.expression(message)?;
         match message {
-            Some(message) => Ok("failwith "
-                .to_doc()
-                .append(self.expression(message.as_ref())?.surround("(", ")"))),
-            None => Ok("failwith \"Not implemented\"".to_doc()),
+            TypedExpr::String { .. } => Ok(doc),
+            _ => Ok(doc.surround("(", ")")),
         }
     }
 
-    fn panic_(&mut self, message: &'a Option<Box<TypedExpr>>) -> Result<Document<'a>> {
-        match message {
-            Some(message) => Ok("failwith "
-                .to_doc()
-                .append(self.expression(message.as_ref())?.surround("(", ")"))),
-            None => Ok("failwith \"Panic encountered\"".to_doc()),
+    /// Raises one of the prelude's Gleam exceptions, with the location of the
+    /// expression that raised it
+    fn raise_gleam_error(
+        &self,
+        exception: &'static str,
+        message: Document<'a>,
+        location: SrcSpan,
+        value: Option<Document<'a>>,
+    ) -> Document<'a> {
+        let line = self
+            .module
+            .ast
+            .type_info
+            .line_numbers
+            .line_number(location.start);
+        let mut args = vec![
+            message,
+            self.string(&self.module.name),
+            self.string(self.current_function),
+            line.to_doc(),
+        ];
+        if let Some(value) = value {
+            args.push(docvec!["box ", value]);
         }
+        docvec![
+            "raise (",
+            exception,
+            join(args, ", ".to_doc()).surround("(", ")"),
+            ")"
+        ]
+    }
+
+    /// `let assert` checks the pattern before binding it, raising a
+    /// `GleamAssertError` if it does not match
+    fn let_assert(
+        &self,
+        pattern: Document<'a>,
+        value: Document<'a>,
+        location: SrcSpan,
+    ) -> Document<'a> {
+        let subject = "_gleam_assert_subject";
+        let error = self.raise_gleam_error(
+            "GleamAssertError",
+            self.string("Pattern match failed, no pattern matched the value."),
+            location,
+            Some(subject.to_doc()),
+        );
+        docvec![
+            "let ",
+            pattern.clone(),
+            " =",
+            docvec![
+                line(),
+                "match ",
+                value,
+                " with",
+                line(),
+                "| ",
+                pattern,
+                " as ",
+                subject,
+                " -> ",
+                subject,
+                line(),
+                "| ",
+                subject,
+                " -> ",
+                error
+            ]
+            .nest(INDENT)
+        ]
     }
 
     fn tuple(&self, elements: impl IntoIterator<Item = Document<'a>>) -> Document<'a> {
@@ -1711,6 +2638,8 @@ impl<'a> Generator<'a> {
         clauses: &'a [TypedClause],
     ) -> Result<Document<'a>> {
         self.context.push(Context::Match);
+        let function_position = self.function_position;
+        self.function_position = Position::NotTail;
         let subjects_doc = if subjects.len() == 1 {
             self.expression(
                 subjects
@@ -1724,6 +2653,7 @@ impl<'a> Generator<'a> {
                 .collect_results()?;
             Ok(self.tuple(items))
         };
+        self.function_position = function_position;
 
         let clauses = join(
             clauses
@@ -1793,8 +2723,10 @@ impl<'a> Generator<'a> {
     fn statement_sequence(&mut self, statements: &'a [TypedStatement]) -> Result<Document<'a>> {
         let documents = statements
             .iter()
-            .map(|e| {
-                let (doc, _) = self.statement(e)?;
+            .enumerate()
+            .map(|(index, e)| {
+                let is_last = index == statements.len() - 1;
+                let (doc, _) = self.statement_in_position(e, is_last, false)?;
                 Ok(doc.group())
             })
             .collect_results()?;
@@ -1869,6 +2801,26 @@ impl<'a> Generator<'a> {
             | ClauseGuard::ModuleSelect { .. } => self.bare_clause_guard(guard),
         }
     }
+    /// Render a guard as an argument to a curried prelude function
+    fn prelude_guard_arg(&mut self, guard: &'a TypedClauseGuard) -> Result<Document<'a>> {
+        match guard {
+            ClauseGuard::Var { .. }
+            | ClauseGuard::TupleIndex { .. }
+            | ClauseGuard::FieldAccess { .. }
+            | ClauseGuard::ModuleSelect { .. } => self.bare_clause_guard(guard),
+            ClauseGuard::Constant(Constant::Int { value, .. } | Constant::Float { value, .. })
+                if !value.starts_with('-') =>
+            {
+                self.bare_clause_guard(guard)
+            }
+            ClauseGuard::Constant(_) | ClauseGuard::Not { .. } => {
+                Ok(self.bare_clause_guard(guard)?.surround("(", ")"))
+            }
+            // Binary operators are already parenthesised
+            _ => self.clause_guard(guard),
+        }
+    }
+
     fn bare_clause_guard(&mut self, guard: &'a TypedClauseGuard) -> Result<Document<'a>> {
         match guard {
             ClauseGuard::Not { expression, .. } => {
@@ -1887,6 +2839,26 @@ impl<'a> Generator<'a> {
                 self.clause_guard(right)?
             ]),
 
+            ClauseGuard::Equals { left, right, .. } if contains_type_variable(&left.type_()) => {
+                Ok(docvec![
+                    "Unchecked.equals (",
+                    self.clause_guard(left)?,
+                    ") (",
+                    self.clause_guard(right)?,
+                    ")"
+                ])
+            }
+
+            ClauseGuard::NotEquals { left, right, .. } if contains_type_variable(&left.type_()) => {
+                Ok(docvec![
+                    "not (Unchecked.equals (",
+                    self.clause_guard(left)?,
+                    ") (",
+                    self.clause_guard(right)?,
+                    "))"
+                ])
+            }
+
             ClauseGuard::Equals { left, right, .. } => Ok(docvec![
                 self.clause_guard(left)?,
                 " = ",
@@ -1951,18 +2923,25 @@ impl<'a> Generator<'a> {
                 self.clause_guard(right)?
             ]),
 
-            ClauseGuard::DivInt { left, right, .. } | ClauseGuard::DivFloat { left, right, .. } => {
-                Ok(docvec![
-                    self.clause_guard(left)?,
-                    " / ",
-                    self.clause_guard(right)?
-                ])
-            }
+            ClauseGuard::DivInt { left, right, .. } => Ok(docvec![
+                "divideInt ",
+                self.prelude_guard_arg(left)?,
+                " ",
+                self.prelude_guard_arg(right)?
+            ]),
+
+            ClauseGuard::DivFloat { left, right, .. } => Ok(docvec![
+                "divideFloat ",
+                self.prelude_guard_arg(left)?,
+                " ",
+                self.prelude_guard_arg(right)?
+            ]),
 
             ClauseGuard::RemainderInt { left, right, .. } => Ok(docvec![
-                self.clause_guard(left)?,
-                " % ",
-                self.clause_guard(right)?
+                "remainderInt ",
+                self.prelude_guard_arg(left)?,
+                " ",
+                self.prelude_guard_arg(right)?
             ]),
 
             // ClauseGuard::Vars are local variables
@@ -1994,6 +2973,33 @@ impl<'a> Generator<'a> {
         left: &'a TypedExpr,
         right: &'a TypedExpr,
     ) -> Result<Document<'a>> {
+        let helper = match name {
+            BinOp::DivInt => Some("divideInt"),
+            BinOp::RemainderInt => Some("remainderInt"),
+            BinOp::DivFloat => Some("divideFloat"),
+            _ => None,
+        };
+
+        // Division is implemented in the prelude so that dividing by zero
+        // returns zero as it does on the other targets instead of throwing
+        if let Some(helper) = helper {
+            let left = self.prelude_call_arg(left)?;
+            let right = self.prelude_call_arg(right)?;
+            return Ok(docvec![helper, " ", left, " ", right]);
+        }
+
+        // Type parameters are declared without an equality constraint, so
+        // values of a generic type are compared structurally at runtime
+        if matches!(name, BinOp::Eq | BinOp::NotEq) && contains_type_variable(&left.type_()) {
+            let left = self.prelude_call_arg(left)?;
+            let right = self.prelude_call_arg(right)?;
+            let equals = docvec!["Unchecked.equals ", left, " ", right];
+            return Ok(match name {
+                BinOp::NotEq => docvec!["not (", equals, ")"],
+                _ => equals,
+            });
+        }
+
         let operand = match name {
             // Boolean logic
             BinOp::And => "&&",
@@ -2013,8 +3019,9 @@ impl<'a> Generator<'a> {
             BinOp::AddInt | BinOp::AddFloat => "+",
             BinOp::SubInt | BinOp::SubFloat => "-",
             BinOp::MultInt | BinOp::MultFloat => "*",
-            BinOp::DivInt | BinOp::DivFloat => "/",
-            BinOp::RemainderInt => "%",
+            BinOp::DivInt | BinOp::DivFloat | BinOp::RemainderInt => {
+                unreachable!("division is handled by the prelude helpers")
+            }
 
             // Strings
             BinOp::Concatenate => "+",
@@ -2027,6 +3034,20 @@ impl<'a> Generator<'a> {
             .append(self.expression(right)?))
     }
 
+    /// Render an argument to a curried prelude function, parenthesised unless
+    /// it is a variable or a non-negative literal
+    fn prelude_call_arg(&mut self, expr: &'a TypedExpr) -> Result<Document<'a>> {
+        match expr {
+            TypedExpr::Var { .. } => self.expression(expr),
+            TypedExpr::Int { value, .. } | TypedExpr::Float { value, .. }
+                if !value.starts_with('-') =>
+            {
+                self.expression(expr)
+            }
+            _ => Ok(self.expression(expr)?.surround("(", ")")),
+        }
+    }
+
     /// Implement pipeline (|>) expressions
     fn pipeline(
         &mut self,
@@ -2035,6 +3056,8 @@ impl<'a> Generator<'a> {
     ) -> Result<Document<'a>> {
         let mut documents = Vec::with_capacity((assignments.len() + 1) * 3);
 
+        let function_position = self.function_position;
+        self.function_position = Position::NotTail;
         for a in assignments {
             self.context.push(Context::Binding);
             let name = self.pattern(&a.pattern)?;
@@ -2043,8 +3066,13 @@ impl<'a> Generator<'a> {
             documents.push(assignment);
             documents.push(line());
         }
+        self.function_position = function_position;
 
-        documents.push(self.expression(finally)?.surround("(", ")"));
+        if self.is_tail_call(finally) {
+            documents.push(self.expression(finally)?);
+        } else {
+            documents.push(self.expression(finally)?.surround("(", ")"));
+        }
 
         Ok(self.wrap_in_begin_end(documents.to_doc()))
     }
@@ -2066,10 +3094,15 @@ impl<'a> Generator<'a> {
 
     fn pattern(&mut self, p: &'a Pattern<Arc<Type>>) -> Result<Document<'a>> {
         match p {
-            Pattern::Int { value, .. } => Ok(self.integer(value)),
+            Pattern::Int {
+                value, int_value, ..
+            } => Ok(self.int_pattern(value, int_value)),
             Pattern::Float { value, .. } => Ok(value.to_doc()),
             Pattern::String { value, .. } => Ok(self.string(value.as_str())),
-            Pattern::Variable { name, .. } => Ok(self.sanitize_name(name).to_doc()),
+            Pattern::Variable { name, .. } => {
+                _ = self.local_variables.insert(name.clone());
+                Ok(self.sanitize_name(name).to_doc())
+            }
             Pattern::Discard { name, .. } => Ok(name.to_doc()),
             Pattern::List { elements, tail, .. } => {
                 let is_nested_list = p.type_().is_nested_list();
@@ -2151,36 +3184,33 @@ impl<'a> Generator<'a> {
             Pattern::Invalid { .. } => panic!("invalid patterns should not reach code generation"),
             Pattern::Assign {
                 name, pattern: p, ..
-            } => Ok(self.pattern(p)?.append(" as ").append(name)),
+            } => {
+                _ = self.local_variables.insert(name.clone());
+                Ok(self.pattern(p)?.append(" as ").append(name))
+            }
 
             Pattern::Constructor {
                 constructor:
-                    pattern_constructor @ Inferred::Known(PatternConstructor {
+                    Inferred::Known(PatternConstructor {
                         name,
                         field_map: Some(ref field_map),
+                        variants_count,
                         ..
                     }),
                 spread,
                 arguments,
                 type_,
-                module,
                 ..
-            } if arguments.len() == field_map.fields.len() => {
-                let (_, type_name) = type_.named_type_name().expect("Expected a named type");
-                let value_constructor = self
-                    .module
-                    .ast
-                    .type_info
-                    .types_value_constructors
-                    .get(&type_name);
-
-                // If there's no constructor found, or more than one possible constructor, we can't print this like a record type
-                if value_constructor.is_none()
-                    || value_constructor.expect("impossible").variants.len() > 1
-                {
-                    return self.constructor_pattern(arguments, pattern_constructor, name, module);
-                }
-
+            } if arguments.len() == field_map.fields.len()
+                && is_record_constructor(
+                    name,
+                    type_,
+                    *variants_count,
+                    arguments.len() as u16,
+                    field_map,
+                ) =>
+            {
+                let qualifier = self.record_label_qualifier(type_);
                 let field_map = invert_field_map(field_map);
 
                 let args = arguments
@@ -2205,7 +3235,9 @@ impl<'a> Generator<'a> {
                         }
                     })
                     .collect_results()?;
-                Ok(join(args, "; ".to_doc()).group().surround("{ ", " }"))
+                Ok(docvec![qualifier, join(args, "; ".to_doc())]
+                    .group()
+                    .surround("{ ", " }"))
             }
 
             Pattern::Constructor { name, type_, .. } if type_.is_bool() && name == "True" => {
@@ -2250,11 +3282,27 @@ impl<'a> Generator<'a> {
         } else {
             join(args, ", ".to_doc()).surround("(", ")")
         };
-        let module = match module {
-            Some((module, _)) => docvec![module, "."],
-            None => nil(),
+        let constructor = match constructor {
+            Inferred::Known(PatternConstructor {
+                module: constructor_module,
+                name: constructor_name,
+                ..
+            }) if constructor_module == PRELUDE_MODULE_NAME
+                && (module.is_some() || constructor_name != name) =>
+            {
+                qualified_prelude_value(constructor_name)
+            }
+            Inferred::Known(PatternConstructor { module, name, .. })
+                if module != PRELUDE_MODULE_NAME && *module != self.module.name =>
+            {
+                docvec![self.sanitize_name(module), ".", self.sanitize_name(name)]
+            }
+            _ => match module {
+                Some((module, _)) if module != PRELUDE_MODULE_NAME => docvec![module, ".", name],
+                Some(_) | None => name.to_doc(),
+            },
         };
-        Ok(docvec![module, name.to_doc(), args].surround("(", ")"))
+        Ok(docvec![constructor, args].surround("(", ")"))
     }
 
     fn type_to_fsharp(&mut self, t: Arc<Type>) -> Document<'a> {
@@ -2263,17 +3311,17 @@ impl<'a> Generator<'a> {
         }
 
         match t.deref() {
-            Type::Named { name, args, .. } => {
-                let name = map_builtin_type_name_to_fsharp(name);
-                // let _fully_qualified_name = match name.as_str() {
-                //     "list" | "string" | "bool" | "int64" | "float" | "unit" => name.to_doc(),
-                //     _ => docvec![self.sanitize_name(module), ".", name],
-                // };
-                // if args.is_empty() {
-                //     docvec![self.sanitize_name(module), ".", name]
-                // } else {
+            Type::Named {
+                name, module, args, ..
+            } => {
+                // Types from other modules are fully qualified as imported
+                // modules may define types with the same name
+                let name = if module == PRELUDE_MODULE_NAME || *module == self.module.name {
+                    map_builtin_type_name_to_fsharp(name, self.config.int_representation).to_doc()
+                } else {
+                    docvec![self.sanitize_name(module), ".", name]
+                };
                 docvec![
-                    // fully_qualified_name,
                     name,
                     if !args.is_empty() {
                         join(
@@ -2285,18 +3333,10 @@ impl<'a> Generator<'a> {
                         nil()
                     }
                 ]
-                // name.to_doc()
-                //     .append("<")
-                //     .append(join(
-                //         args.iter().map(|arg| self.type_to_fsharp(arg.clone())),
-                //         ", ".to_doc(),
-                //     ))
-                //     .append(">")
-                //}
-            }
-            Type::Fn { args, retrn } => self.function_type(true, args, retrn),
+            }
+            Type::Fn { args, retrn } => self.function_type(args, retrn),
             Type::Tuple { elems } => join(
-                elems.iter().map(|t| self.type_to_fsharp(t.clone())),
+                elems.iter().map(|t| self.type_to_fsharp_operand(t.clone())),
                 " * ".to_doc(),
             )
             .surround("(", ")"),
@@ -2305,15 +3345,82 @@ impl<'a> Generator<'a> {
                 match borrowed.deref() {
                     TypeVar::Link { type_ } => self.type_to_fsharp(type_.clone()),
                     TypeVar::Unbound { id } | TypeVar::Generic { id } => {
-                        sanitize_type_var(&self.printer.type_variable(*id)).to_doc()
+                        let name = sanitize_type_var(&self.printer.type_variable(*id));
+                        match &self.function_type_parameters {
+                            Some(parameters) if !parameters.contains(&name) => "_".to_doc(),
+                            _ => name.to_doc(),
+                        }
                     }
                 }
             }
         }
     }
 
-    fn get_all_type_variables(&mut self, type_: Arc<Type>) -> HashSet<EcoString> {
-        fn visit(type_: Arc<Type>, printer: &mut Printer<'_>, type_vars: &mut HashSet<EcoString>) {
+    /// The type variables of a function's signature, in the order they first
+    /// appear
+    fn signature_type_variables(
+        &mut self,
+        arguments: &[TypedArg],
+        return_type: &Arc<Type>,
+    ) -> Vec<EcoString> {
+        let mut type_variables = vec![];
+        for type_ in arguments
+            .iter()
+            .map(|arg| &arg.type_)
+            .chain(std::iter::once(return_type))
+        {
+            for type_variable in self.get_all_type_variables(type_.clone()) {
+                if !type_variables.contains(&type_variable) {
+                    type_variables.push(type_variable);
+                }
+            }
+        }
+        type_variables
+    }
+
+    /// The type variables of a function signature that may be used as the key
+    /// of a `Dict` or `Set`, which F# requires to support comparison. This is
+    /// `None` if they can't be determined.
+    fn comparison_type_variables(&mut self, function_name: &str) -> Option<Vec<EcoString>> {
+        let ids = self
+            .comparison_constraints
+            .function_type_variables(function_name)?
+            .to_vec();
+        Some(
+            ids.into_iter()
+                .map(|id| sanitize_type_var(&self.printer.type_variable(id)))
+                .collect(),
+        )
+    }
+
+    /// A function's type parameters are left for F# to infer when it isn't
+    /// known which of them need to support comparison
+    fn function_type_params(
+        &self,
+        type_parameters: &[EcoString],
+        comparison_type_parameters: Option<&[EcoString]>,
+    ) -> Document<'a> {
+        let Some(comparison_type_parameters) = comparison_type_parameters else {
+            return nil();
+        };
+        if type_parameters.is_empty() {
+            return nil();
+        }
+        let type_parameters = join(
+            type_parameters.iter().map(|p| p.clone().to_doc()),
+            ", ".to_doc(),
+        );
+        let constraints = comparison_constraints(
+            comparison_type_parameters
+                .iter()
+                .map(|p| p.clone().to_doc())
+                .collect(),
+        );
+        docvec!["<", type_parameters, constraints, ">"]
+    }
+
+    fn get_all_type_variables(&mut self, type_: Arc<Type>) -> Vec<EcoString> {
+        fn visit(type_: Arc<Type>, printer: &mut Printer<'_>, type_vars: &mut Vec<EcoString>) {
             match type_.as_ref() {
                 Type::Var { type_ } => {
                     let borrowed = type_.borrow();
@@ -2322,7 +3429,7 @@ impl<'a> Generator<'a> {
                             visit(type_.clone(), printer, type_vars);
                         }
                         TypeVar::Unbound { id } | TypeVar::Generic { id } => {
-                            _ = type_vars.insert(sanitize_type_var(&printer.type_variable(*id)));
+                            type_vars.push(sanitize_type_var(&printer.type_variable(*id)));
                         }
                     }
                 }
@@ -2345,26 +3452,29 @@ impl<'a> Generator<'a> {
             }
         }
 
-        let mut type_vars = HashSet::new();
+        let mut type_vars = vec![];
         visit(type_, &mut self.printer, &mut type_vars);
         type_vars
     }
 
-    fn function_type(
-        &mut self,
-        curried: bool,
-        args: &[Arc<Type>],
-        retrn: &Arc<Type>,
-    ) -> Document<'a> {
+    /// A type used as a function argument or tuple element, where function
+    /// types need parentheses
+    fn type_to_fsharp_operand(&mut self, t: Arc<Type>) -> Document<'a> {
+        if t.is_fun() {
+            self.type_to_fsharp(t).surround("(", ")")
+        } else {
+            self.type_to_fsharp(t)
+        }
+    }
+
+    fn function_type(&mut self, args: &[Arc<Type>], retrn: &Arc<Type>) -> Document<'a> {
         let arg_types = args
             .iter()
-            .map(|arg| self.type_to_fsharp(arg.clone()))
+            .map(|arg| self.type_to_fsharp_operand(arg.clone()))
             .collect::<Vec<Document<'a>>>();
 
         let arg_types = if arg_types.is_empty() {
             "unit".to_doc()
-        } else if !curried {
-            join(arg_types, " * ".to_doc())
         } else {
             join(arg_types, " -> ".to_doc())
         };
@@ -2390,326 +3500,185 @@ impl<'a> Generator<'a> {
 
     fn bit_array_pattern(
         &mut self,
-        segments: &'a [BitArraySegment<Pattern<Arc<Type>>, Arc<Type>>],
+        segments: &'a [TypedPatternBitArraySegment],
     ) -> Result<Document<'a>> {
-        if segments.is_empty() {
-            return Ok(self.surround_if_not_match("BitArray.Empty".to_doc()));
+        // Each segment is matched by an active pattern which returns the bits
+        // that follow it, so the segments are nested from the last to the first
+        let mut rest = "BitArray.Empty".to_doc();
+        for (index, segment) in segments.iter().enumerate().rev() {
+            let is_last = index + 1 == segments.len();
+            rest = self.bit_array_segment_pattern(segment, rest, is_last)?;
         }
+        Ok(self.surround_if_not_match(rest))
+    }
 
-        let mut all_patterns = segments
-            .iter()
-            .map(|s| self.bit_array_segment_pattern(s))
-            .collect_results()?;
+    fn bit_array_segment_pattern(
+        &mut self,
+        segment: &'a TypedPatternBitArraySegment,
+        rest: Document<'a>,
+        is_last: bool,
+    ) -> Result<Document<'a>> {
+        let has_option =
+            |f: fn(&BitArrayOption<TypedPattern>) -> bool| segment.options.iter().any(f);
+        let endianness = bit_array_endianness(&segment.options);
+        let value = self.pattern(&segment.value)?;
+
+        let utf = if has_option(|o| matches!(o, BitArrayOption::Utf8 { .. })) {
+            Some((
+                "BitArray.Utf8Segment",
+                "BitArray.Utf8CodepointSegment",
+                None,
+            ))
+        } else if has_option(|o| matches!(o, BitArrayOption::Utf16 { .. })) {
+            let endianness = Some(endianness.clone());
+            Some((
+                "BitArray.Utf16Segment",
+                "BitArray.Utf16CodepointSegment",
+                endianness,
+            ))
+        } else if has_option(|o| matches!(o, BitArrayOption::Utf32 { .. })) {
+            let endianness = Some(endianness.clone());
+            Some((
+                "BitArray.Utf32Segment",
+                "BitArray.Utf32CodepointSegment",
+                endianness,
+            ))
+        } else {
+            None
+        };
 
-        if all_patterns.len() == 1 {
-            let (pattern, size) = all_patterns.first().expect("impossible");
-            match size {
-                Some(size) => {
-                    return Ok(self.surround_if_not_match(docvec![
-                        "BitArray.Sections [",
-                        size.clone(),
-                        "] [",
-                        pattern.clone(),
-                        "]",
-                    ]));
-                }
-                None => {
-                    return Ok(self.surround_if_not_match(docvec![
-                        "BitArray.Sections [] [",
-                        pattern.clone(),
-                        "]",
-                    ]))
+        if let (Some((pattern, _, endianness)), Pattern::String { value, .. }) =
+            (&utf, segment.value.as_ref())
+        {
+            // String literals are matched against their encoded bytes
+            let args = match endianness {
+                Some(endianness) => {
+                    docvec![self.string(value), ", ", endianness.clone()].surround("(", ")")
                 }
-            }
+                None => self.string(value),
+            };
+            return Ok(docvec![*pattern, " ", args, " ", rest.surround("(", ")")]);
         }
 
-        let tail_pattern = all_patterns.last();
-
-        let mut tail = None;
-        if let Some((t, None)) = tail_pattern {
-            tail = Some(t.clone());
-            _ = all_patterns.pop();
-        }
+        let (pattern, args) = match utf {
+            // Any other UTF segment is a discard, which matches a single codepoint
+            Some((_, pattern, endianness)) => (pattern, endianness),
 
-        let mut sizes: Vec<Document<'a>> = Vec::with_capacity(all_patterns.len());
-        let mut patterns: Vec<Document<'a>> = Vec::with_capacity(all_patterns.len());
+            None if has_option(|o| matches!(o, BitArrayOption::Utf8Codepoint { .. })) => {
+                ("BitArray.Utf8CodepointSegment", None)
+            }
+            None if has_option(|o| matches!(o, BitArrayOption::Utf16Codepoint { .. })) => {
+                ("BitArray.Utf16CodepointSegment", Some(endianness))
+            }
+            None if has_option(|o| matches!(o, BitArrayOption::Utf32Codepoint { .. })) => {
+                ("BitArray.Utf32CodepointSegment", Some(endianness))
+            }
 
-        for (index, (pattern, size)) in all_patterns.into_iter().enumerate() {
-            match size {
-                Some(size) if index < sizes.len() => {
-                    sizes.insert(index, size);
-                    patterns.insert(index, pattern);
-                }
-                Some(_) => {
-                    // sizes.push(docvec!["BitArraySegment.SizeOf(", pattern.clone(), ")"]);
-                    // patterns.push(pattern);
-                    return self.make_error(Error::InvalidBitArrayPattern {
-                        reason: pattern.to_pretty_string(100).into(),
-                        location: segments.get(index).expect("impossible").location,
-                    });
-                }
-                None => {
-                    // return self.make_error(Error::InvalidBitArrayPattern {
-                    //     location: segments.get(index).expect("impossible").location,
-                    // });
+            None if segment.type_.is_float() => {
+                match self.bit_array_pattern_size(segment, "64L")? {
+                    (size, 1) => {
+                        let args = docvec![size, ", ", endianness].surround("(", ")");
+                        ("BitArray.FloatSegment", Some(args))
+                    }
+                    (size, unit) => {
+                        let args = docvec![size, ", ", unit, "L, ", endianness].surround("(", ")");
+                        ("BitArray.FloatUnitsSegment", Some(args))
+                    }
                 }
             }
-        }
-
-        Ok(self.surround_if_not_match(docvec![
-            "BitArray.Sections [",
-            join(sizes, "; ".to_doc()),
-            "] [",
-            join(patterns, "; ".to_doc()),
-            tail.map(|t| docvec!["; ", t]).unwrap_or_else(nil),
-            "]",
-        ]))
-    }
 
-    fn get_bit_array_pattern(
-        &mut self,
-        segment: &'a BitArraySegment<Pattern<Arc<Type>>, Arc<Type>>,
-    ) -> Result<BitArrayPattern<'a>> {
-        const FLOAT_SIZE: &str = "sizeof<float>";
-        const INT_SIZE: &str = "sizeof<int64>";
-        let mut size: Option<Document<'a>> = None;
-        let mut value_type = BitArraySegmentType::Bits;
-        match segment.value.as_ref() {
-            Pattern::VarUsage { .. } => {
-                value_type = BitArraySegmentType::Binding;
-                size = Some(docvec![
-                    "BitArray.SizeOf(",
-                    self.pattern(&segment.value)?,
-                    ")"
-                ]);
-            }
-            Pattern::Discard { type_, .. } | Pattern::Variable { type_, .. } => {
-                match type_.as_ref() {
-                    Type::Named { name, .. } => match name.as_str() {
-                        "Int" => value_type = BitArraySegmentType::Int64,
-                        "Float" => value_type = BitArraySegmentType::Float,
-                        "BitArray" => value_type = BitArraySegmentType::Bits,
-                        type_name => {
-                            println!("type_name: {}", type_name);
-                            value_type = BitArraySegmentType::Binding;
-                            size = None
-                        }
-                    },
-                    _ => {
-                        value_type = BitArraySegmentType::Binding;
-                        size = None
+            None if segment.type_.is_int() => {
+                let signed = if has_option(|o| matches!(o, BitArrayOption::Signed { .. })) {
+                    "true"
+                } else {
+                    "false"
+                };
+                let big_int = self.config.int_representation == FSharpIntRepresentation::BigInt;
+                match self.bit_array_pattern_size(segment, "8L")? {
+                    (size, 1) => {
+                        let args = docvec![size, ", ", signed, ", ", endianness].surround("(", ")");
+                        let pattern = if big_int {
+                            "BitArray.BigIntSegment"
+                        } else {
+                            "BitArray.IntSegment"
+                        };
+                        (pattern, Some(args))
+                    }
+                    (size, unit) => {
+                        let args = docvec![size, ", ", unit, "L, ", signed, ", ", endianness]
+                            .surround("(", ")");
+                        let pattern = if big_int {
+                            "BitArray.BigIntUnitsSegment"
+                        } else {
+                            "BitArray.IntUnitsSegment"
+                        };
+                        (pattern, Some(args))
                     }
                 }
             }
-            Pattern::Int { .. } => {
-                value_type = BitArraySegmentType::Int64;
-                size = Some(INT_SIZE.to_doc())
-            }
-            Pattern::Float { .. } => {
-                value_type = BitArraySegmentType::Float;
-                size = Some(FLOAT_SIZE.to_doc())
-            }
-            Pattern::String { .. } => {
-                value_type = BitArraySegmentType::Utf8;
-                size = Some(docvec![
-                    "BitArray.SizeOfUtf8(",
-                    self.pattern(&segment.value)?,
-                    ")"
-                ]);
-            }
-            _ => {}
-        }
-
-        let mut signed = None;
-        let mut endianness = None;
-        let mut unit = None;
 
-        for option in &segment.options {
-            match option {
-                BitArrayOption::Size { value, .. } => {
-                    size = Some(self.pattern(value.as_ref())?);
-                }
-                BitArrayOption::Int { .. } => {
-                    size = Some(INT_SIZE.to_doc());
-                }
-                BitArrayOption::Float { .. } => {
-                    size = Some(FLOAT_SIZE.to_doc());
-                }
-                BitArrayOption::Unsigned { .. } => {
-                    signed = Some(false);
-                }
-                BitArrayOption::Signed { .. } => {
-                    signed = Some(true);
-                }
-                BitArrayOption::Big { .. } => {
-                    endianness = Some(BitArrayEndianness::Big);
-                }
-                BitArrayOption::Little { .. } => {
-                    endianness = Some(BitArrayEndianness::Little);
-                }
-                BitArrayOption::Native { .. } => {
-                    endianness = Some(BitArrayEndianness::Native);
-                }
-                BitArrayOption::Bytes { .. } => {
-                    value_type = BitArraySegmentType::Bytes;
-                }
-                BitArrayOption::Bits { .. } => {
-                    value_type = BitArraySegmentType::Bits;
-                }
-                BitArrayOption::Utf8 { .. } => {
-                    value_type = BitArraySegmentType::Utf8;
-                }
-                BitArrayOption::Utf16 { .. } => {
-                    value_type = BitArraySegmentType::Utf16;
-                }
-                BitArrayOption::Utf32 { .. } => {
-                    value_type = BitArraySegmentType::Utf32;
-                }
-                BitArrayOption::Utf8Codepoint { .. } => {
-                    value_type = BitArraySegmentType::Utf8Codepoint;
-                }
-                BitArrayOption::Utf16Codepoint { .. } => {
-                    value_type = BitArraySegmentType::Utf16Codepoint;
-                }
-                BitArrayOption::Utf32Codepoint { .. } => {
-                    value_type = BitArraySegmentType::Utf32Codepoint;
+            // An unsized bits or bytes segment at the end matches the rest of
+            // the bit array, which for bytes must be a whole number of bytes
+            None if is_last && !has_option(|o| matches!(o, BitArrayOption::Size { .. })) => {
+                if has_option(|o| matches!(o, BitArrayOption::Bytes { .. })) {
+                    return Ok(docvec!["BitArray.WholeBytes ", value.surround("(", ")")]);
                 }
-                BitArrayOption::Unit { value, .. } => unit = Some(value),
+                return Ok(value);
             }
-        }
 
-        let size = match (size, unit) {
-            (Some(size), Some(unit)) => Some(docvec![size, " * ", unit].surround("(", ")")),
-            (Some(size), None) => Some(size.to_doc()),
-            (None, Some(_)) => None,
-            _ => None,
+            None => match self.bit_array_pattern_size(segment, "0L")? {
+                (size, 1) => ("BitArray.BitsSegment", Some(size)),
+                (size, 8) => ("BitArray.BytesSegment", Some(size)),
+                (size, unit) => (
+                    "BitArray.UnitsSegment",
+                    Some(docvec![size, ", ", unit, "L"].surround("(", ")")),
+                ),
+            },
         };
 
-        match (value_type, size) {
-            (BitArraySegmentType::Binding, size) => {
-                if let Some(signed) = signed {
-                    let size = if signed {
-                        "sizeof<int64>".to_doc()
-                    } else {
-                        "sizeof<uint64>".to_doc()
-                    };
-                    Ok(BitArrayPattern::Int64 {
-                        size: Some(size),
-                        signed,
-                    })
-                } else {
-                    Ok(BitArrayPattern::Binding { size, signed })
-                }
-            }
-            (BitArraySegmentType::Int64, size) => Ok(BitArrayPattern::Int64 {
-                signed: signed.unwrap_or(true),
-                size,
-            }),
-            (BitArraySegmentType::Float, size) => Ok(BitArrayPattern::Float {
-                signed: signed.unwrap_or(true),
-                size,
-            }),
-            (BitArraySegmentType::Utf8, size) => Ok(BitArrayPattern::Utf8 { endianness, size }),
-            (BitArraySegmentType::Utf16, size) => Ok(BitArrayPattern::Utf16 { endianness, size }),
-            (BitArraySegmentType::Utf32, size) => Ok(BitArrayPattern::Utf32 { endianness, size }),
-            (BitArraySegmentType::Utf8Codepoint, size) => {
-                Ok(BitArrayPattern::Utf8Codepoint { size })
-            }
-            (BitArraySegmentType::Utf16Codepoint, size) => {
-                Ok(BitArrayPattern::Utf16Codepoint { size })
-            }
-            (BitArraySegmentType::Utf32Codepoint, size) => {
-                Ok(BitArrayPattern::Utf32Codepoint { size })
-            }
-            (BitArraySegmentType::Bytes, size) => Ok(BitArrayPattern::Bytes { size }),
-            (BitArraySegmentType::Bits, size) => Ok(BitArrayPattern::Bits { size }),
-        }
+        let binding = docvec![value, ", ", rest].surround("(", ")");
+        Ok(match args {
+            Some(args) => docvec![pattern, " ", args, " ", binding],
+            None => docvec![pattern, " ", binding],
+        })
     }
 
-    fn bit_array_segment_pattern(
+    /// The size of a bit array pattern segment, along with the unit it is
+    /// measured in. Active pattern arguments can't contain arithmetic, so
+    /// literal sizes are converted to bits here while variable sizes keep
+    /// their unit.
+    fn bit_array_pattern_size(
         &mut self,
-        segment: &'a BitArraySegment<Pattern<Arc<Type>>, Arc<Type>>,
-    ) -> Result<(Document<'a>, Option<Document<'a>>)> {
-        let pattern = self.get_bit_array_pattern(segment)?;
-
-        let pattern_doc = self.pattern(&segment.value)?;
-
-        match pattern {
-            BitArrayPattern::Binding {
-                size: Some(size), ..
-            } => Ok((
-                docvec![
-                    "BitArraySegment.WithLength(",
-                    size.clone(),
-                    ") as ",
-                    Some(self.pattern(&segment.value)?)
-                ],
-                Some(size),
-            )),
+        segment: &'a TypedPatternBitArraySegment,
+        default: &'static str,
+    ) -> Result<(Document<'a>, i64)> {
+        let is_bytes = segment
+            .options
+            .iter()
+            .any(|option| matches!(option, BitArrayOption::Bytes { .. }));
+        let unit = segment
+            .options
+            .iter()
+            .find_map(|option| match option {
+                BitArrayOption::Unit { value, .. } => Some(*value as i64),
+                _ => None,
+            })
+            .unwrap_or(if is_bytes { 8 } else { 1 });
+        let size = segment.options.iter().find_map(|option| match option {
+            BitArrayOption::Size { value, .. } => Some(value.as_ref()),
+            _ => None,
+        });
 
-            BitArrayPattern::Binding {
-                signed: Some(signed),
-                ..
-            } => {
-                let size = if signed {
-                    "sizeof<int64>".to_doc()
-                } else {
-                    "sizeof<uint64>".to_doc()
-                };
-                Ok((
-                    docvec![
-                        "BitArraySegment.WithLength(",
-                        size.clone(),
-                        ") as ",
-                        Some(self.pattern(&segment.value)?)
-                    ],
-                    Some(size),
-                ))
-            }
-            BitArrayPattern::Binding { .. } => Ok((pattern_doc, None)),
-            BitArrayPattern::Int64 {
-                signed: false,
-                size,
-            } => Ok((
-                docvec!["BitArraySegment.UnsignedInt64(", pattern_doc, ")"],
-                size,
-            )),
-            BitArrayPattern::Int64 { size, .. } => {
-                Ok((docvec!["BitArraySegment.Int64(", pattern_doc, ")"], size))
+        match size {
+            None => Ok((default.to_doc(), 1)),
+            Some(Pattern::Int { int_value, .. }) => {
+                let bits = int_value * unit;
+                Ok((EcoString::from(format!("{bits}L")).to_doc(), 1))
             }
-            BitArrayPattern::Float { signed: false, .. } => self.make_error(Error::Unsupported {
-                feature: ".NET does not support unsigned floating-point numbers".into(),
-                location: segment.location,
-            }),
-            BitArrayPattern::Float { size, .. } => {
-                Ok((docvec!["BitArraySegment.Float64(", pattern_doc, ")"], size))
+            Some(size) => {
+                let size = self.pattern(size)?;
+                Ok((self.int_to_int64(size, "bigintSegmentSize"), unit))
             }
-            BitArrayPattern::Utf8 { size, .. } => Ok((
-                docvec!["BitArraySegment.Utf8String(", pattern_doc, ")"],
-                size,
-            )),
-            BitArrayPattern::Utf16 { size, .. } => Ok((pattern_doc, size)),
-            BitArrayPattern::Utf32 { size, .. } => Ok((pattern_doc, size)),
-            BitArrayPattern::Utf8Codepoint { size } => Ok((pattern_doc, size)),
-            BitArrayPattern::Utf16Codepoint { size } => Ok((pattern_doc, size)),
-            BitArrayPattern::Utf32Codepoint { size } => Ok((pattern_doc, size)),
-            BitArrayPattern::Bytes { size } => Ok((pattern_doc, size)),
-            BitArrayPattern::Bits { size: None } => {
-                Ok((docvec!["BitArraySegment.Bits(", pattern_doc, ")"], None))
-            }
-            BitArrayPattern::Bits { size: Some(size) } => Ok((
-                docvec![
-                    "BitArraySegment.SizedBits(",
-                    size.clone(),
-                    ") as ",
-                    pattern_doc
-                ],
-                Some(size),
-            )),
-            // unknown => self.make_error(Error::Unsupported {
-            //     feature: format!("Unsupported bit array pattern: {:?}", unknown).into(),
-            //     location: segment.location,
-            // }),
         }
     }
 
@@ -2718,6 +3687,12 @@ impl<'a> Generator<'a> {
         constant: &'a ModuleConstant<Arc<Type>, EcoString>,
     ) -> Result<Document<'a>> {
         let attr = match constant.value.deref() {
+            // BigInteger values cannot be compile time literals
+            Constant::Int { .. }
+                if self.config.int_representation == FSharpIntRepresentation::BigInt =>
+            {
+                nil()
+            }
             Constant::Int { .. } | Constant::Float { .. } | Constant::String { .. } => {
                 docvec!["[<Literal>]", line()]
             }
@@ -2743,7 +3718,9 @@ impl<'a> Generator<'a> {
     fn constant_expression(&mut self, expression: &'a TypedConstant) -> Result<Document<'a>> {
         self.context.push(Context::Expression);
         let res = match expression {
-            Constant::Int { value, .. } => Ok(self.integer(value)),
+            Constant::Int {
+                value, int_value, ..
+            } => Ok(self.integer(value, int_value)),
             Constant::Float { value, .. } => Ok(value.to_doc()),
             Constant::String { value, .. } => Ok(self.string(value)),
             Constant::Tuple { elements, .. } => {
@@ -2770,14 +3747,33 @@ impl<'a> Generator<'a> {
             }
             Constant::Record { type_, .. } if type_.is_nil() => Ok("()".to_doc()),
 
+            // Prelude constructors used with their module or imported with a
+            // different name
             Constant::Record {
-                args,
                 module,
-                name: record_name,
+                name,
+                tag,
+                type_,
+                args,
+                ..
+            } if args.is_empty()
+                && (module.is_some() || name != tag)
+                && type_
+                    .return_type()
+                    .unwrap_or_else(|| type_.clone())
+                    .named_type_name()
+                    .is_some_and(|(module, _)| module == PRELUDE_MODULE_NAME) =>
+            {
+                Ok(qualified_prelude_value(tag))
+            }
+
+            Constant::Record {
+                args,
+                tag,
                 type_,
                 field_map,
                 ..
-            } => self.constant_record_expression(record_name, args, type_, module, field_map),
+            } => self.constant_record_expression(tag, args, type_, field_map),
 
             Constant::BitArray { segments, .. } => self.constant_bit_array_expression(segments),
 
@@ -2837,26 +3833,35 @@ impl<'a> Generator<'a> {
         record_name: &'a EcoString,
         args: &'a [CallArg<TypedConstant>],
         type_: &'a Arc<Type>,
-        module: &'a Option<(EcoString, SrcSpan)>,
         field_map: &'a Option<FieldMap>,
     ) -> Result<Document<'a>> {
+        let record_type = type_.return_type().unwrap_or_else(|| type_.clone());
+        // Constructors from other modules are qualified with the module they
+        // are defined in, as they may have been imported unqualified
+        let module = match record_type.named_type_name() {
+            Some((module, _)) if module != PRELUDE_MODULE_NAME && module != self.module.name => {
+                Some(self.module_reference(&module))
+            }
+            _ => None,
+        };
+
         if let Some(constructor) = self.module.ast.type_info.values.get(record_name) {
             if let ValueConstructorVariant::Record {
                 name,
-                constructors_count,
+                variants_count,
                 field_map: Some(field_map),
                 arity,
                 ..
             } = &constructor.variant
             {
-                // Is a genuine record constructor if:
-                // only one constructor exists
-                // constructor name is the same as the type name
-                // All fields have labels
-
-                if *constructors_count == 1u16
-                    && name == record_name
-                    && *arity == field_map.fields.len() as u16
+                if module.is_none()
+                    && is_record_constructor(
+                        name,
+                        &constructor.type_,
+                        *variants_count,
+                        *arity,
+                        field_map,
+                    )
                 {
                     let field_map = invert_field_map(field_map);
 
@@ -2882,7 +3887,7 @@ impl<'a> Generator<'a> {
         &mut self,
         type_: &'a Arc<Type>,
         record_name: &'a EcoString,
-        module: &'a Option<(EcoString, SrcSpan)>,
+        module: Option<Document<'a>>,
         field_map: &'a Option<FieldMap>,
         args: &'a [CallArg<TypedConstant>],
     ) -> Result<Document<'a>> {
@@ -2898,10 +3903,10 @@ impl<'a> Generator<'a> {
                 return Ok("undefined".to_doc());
             } else if arity == 0 {
                 return match module {
-                    Some((module, _)) => Ok(docvec![module, ".", record_name, "()"]),
+                    Some(module) => Ok(docvec![module, ".", record_name, "()"]),
                     None => Ok(docvec![record_name, "()"]),
                 };
-            } else if let Some((module, _)) = module {
+            } else if let Some(module) = module {
                 return Ok(docvec![module, ".", self.sanitize_name(record_name)]);
             } else {
                 return Ok(self.sanitize_name(record_name).to_doc());
@@ -2909,7 +3914,10 @@ impl<'a> Generator<'a> {
         }
 
         if field_map.is_none() && args.is_empty() {
-            return Ok(self.sanitize_name(record_name).to_doc());
+            return Ok(match module {
+                Some(module) => docvec![module, ".", self.sanitize_name(record_name)],
+                None => self.sanitize_name(record_name),
+            });
         }
 
         let field_values = args
@@ -2917,16 +3925,12 @@ impl<'a> Generator<'a> {
             .map(|arg| self.constant_expression(&arg.value))
             .collect_results()?;
 
-        Ok(self.construct_type(
-            module.as_ref().map(|(module, _)| module.as_str()),
-            record_name,
-            field_values,
-        ))
+        Ok(self.construct_type(module, record_name, field_values))
     }
 
     fn construct_type(
         &mut self,
-        module: Option<&'a str>,
+        module: Option<Document<'a>>,
         name: &'a str,
         arguments: impl IntoIterator<Item = Document<'a>>,
     ) -> Document<'a> {
@@ -2965,6 +3969,10 @@ pub enum Error {
         reason: EcoString,
         location: SrcSpan,
     },
+    InvalidBitArraySegment {
+        reason: EcoString,
+        location: SrcSpan,
+    },
     Unsupported {
         feature: EcoString,
         location: SrcSpan,
@@ -2978,6 +3986,29 @@ fn invert_field_map(field_map: &FieldMap) -> HashMap<&u32, &EcoString> {
         .collect::<HashMap<_, _>>()
 }
 
+/// Converts a Gleam module path such as `my/module` into the F# name it is
+/// compiled to, escaping any reserved words.
+/// Whether the module of an F# external is the path of an F# file, rather
+/// than the name of a .NET namespace, module or type.
+pub fn is_external_file_path(module: &str) -> bool {
+    module.contains('/') || module.contains('\\')
+}
+
+pub fn sanitize_module_name(value: &str) -> EcoString {
+    let mapped = value
+        .split("/")
+        .map(|s| {
+            if is_reserved_word(s) {
+                format!("``{s}``")
+            } else {
+                String::from(s)
+            }
+        })
+        .join(".");
+
+    EcoString::from(mapped)
+}
+
 fn is_reserved_word(name: &str) -> bool {
     matches!(
         name,
@@ -3085,10 +4116,54 @@ fn sanitize_type_var(name: &EcoString) -> EcoString {
     }
 }
 
+/// Whether a type is generic, or has generic type arguments
+fn contains_type_variable(type_: &Type) -> bool {
+    match type_ {
+        Type::Var { type_ } => match type_.borrow().deref() {
+            TypeVar::Link { type_ } => contains_type_variable(type_),
+            TypeVar::Unbound { .. } | TypeVar::Generic { .. } => true,
+        },
+        Type::Named { args, .. } => args.iter().any(|arg| contains_type_variable(arg)),
+        Type::Tuple { elems } => elems.iter().any(|elem| contains_type_variable(elem)),
+        Type::Fn { args, retrn } => {
+            args.iter().any(|arg| contains_type_variable(arg)) || contains_type_variable(retrn)
+        }
+    }
+}
+
+/// The `when` clause constraining the given type parameters to support
+/// comparison
+fn comparison_constraints<'a>(type_parameters: Vec<Document<'a>>) -> Document<'a> {
+    if type_parameters.is_empty() {
+        return nil();
+    }
+    docvec![
+        " when ",
+        join(
+            type_parameters
+                .into_iter()
+                .map(|p| docvec![p, ": comparison"]),
+            " and ".to_doc(),
+        )
+    ]
+}
+
+/// The type of the elements of a list type
+fn list_element_type(type_: &Arc<Type>) -> Option<Arc<Type>> {
+    match type_.as_ref() {
+        Type::Named { args, .. } if type_.is_list() => args.first().cloned(),
+        Type::Var { type_ } => match type_.borrow().deref() {
+            TypeVar::Link { type_ } => list_element_type(type_),
+            TypeVar::Unbound { .. } | TypeVar::Generic { .. } => None,
+        },
+        _ => None,
+    }
+}
+
 fn unicode_escape_sequence_pattern() -> &'static Regex {
     static PATTERN: OnceLock<Regex> = OnceLock::new();
     PATTERN.get_or_init(|| {
-        Regex::new(r#"(\\+)(u)\{(\S+)\}"#)
+        Regex::new(r#"(\\+)(u)\{([0-9a-fA-F]{1,6})\}"#)
             .expect("Unicode escape sequence regex cannot be constructed")
     })
 }
@@ -3144,9 +4219,15 @@ let Uri(scheme, userinfo, host, port, path, query, fragment) : Uri = { scheme =
     }
 }
 
-fn map_builtin_type_name_to_fsharp(name: &EcoString) -> EcoString {
+fn map_builtin_type_name_to_fsharp(
+    name: &EcoString,
+    int_representation: FSharpIntRepresentation,
+) -> EcoString {
     match name.as_str() {
-        "Int" | "int" => EcoString::from("int64"),
+        "Int" | "int" => match int_representation {
+            FSharpIntRepresentation::Int64 => EcoString::from("int64"),
+            FSharpIntRepresentation::BigInt => EcoString::from("bigint"),
+        },
         "Float" | "float" => EcoString::from("float"),
         "String" | "string" => EcoString::from("string"),
         "Bool" | "bool" => EcoString::from("bool"),
@@ -3163,12 +4244,6 @@ enum BitArraySegmentKind {
     BitArray,
     String,
     UtfCodepoint,
-    Utf8,
-    Utf16,
-    Utf32,
-    Utf8Codepoint,
-    Utf16Codepoint,
-    Utf32Codepoint,
     NamedInvalid(EcoString),
     Invalid,
 }
@@ -3181,12 +4256,6 @@ fn type_to_bit_array_segment_kind(type_: Arc<Type>) -> BitArraySegmentKind {
             "BitArray" => BitArraySegmentKind::BitArray,
             "String" => BitArraySegmentKind::String,
             "UtfCodepoint" => BitArraySegmentKind::UtfCodepoint,
-            "Utf8" => BitArraySegmentKind::Utf8,
-            "Utf16" => BitArraySegmentKind::Utf16,
-            "Utf32" => BitArraySegmentKind::Utf32,
-            "Utf8Codepoint" => BitArraySegmentKind::Utf8Codepoint,
-            "Utf16Codepoint" => BitArraySegmentKind::Utf16Codepoint,
-            "Utf32Codepoint" => BitArraySegmentKind::Utf32Codepoint,
             _ => BitArraySegmentKind::NamedInvalid(name.clone()),
         },
         Type::Fn { retrn, .. } => type_to_bit_array_segment_kind(retrn.clone()),
@@ -3233,12 +4302,6 @@ fn constant_bit_array_segment_kind(segment: &TypedConstantBitArraySegment) -> Bi
                 "BitArray" => BitArraySegmentKind::BitArray,
                 "String" => BitArraySegmentKind::String,
                 "UtfCodepoint" => BitArraySegmentKind::UtfCodepoint,
-                "Utf8" => BitArraySegmentKind::Utf8,
-                "Utf16" => BitArraySegmentKind::Utf16,
-                "Utf32" => BitArraySegmentKind::Utf32,
-                "Utf8Codepoint" => BitArraySegmentKind::Utf8Codepoint,
-                "Utf16Codepoint" => BitArraySegmentKind::Utf16Codepoint,
-                "Utf32Codepoint" => BitArraySegmentKind::Utf32Codepoint,
                 _ => BitArraySegmentKind::NamedInvalid(name.clone()),
             },
             _ => BitArraySegmentKind::Invalid,
@@ -3277,85 +4340,129 @@ impl<T, U: IntoIterator<Item = Result<T>>> ResultMap<T> for U {
     }
 }
 
-#[derive(Debug, Clone)]
-enum BitArraySegmentType {
-    Int64,
-    Float,
-    Bytes,
-    Bits,
-    Utf8,
-    Utf16,
-    Utf32,
-    Utf8Codepoint,
-    Utf16Codepoint,
-    Utf32Codepoint,
-    Binding,
+fn bit_array_endianness<'a, Value>(options: &[BitArrayOption<Value>]) -> Document<'a> {
+    let endianness = options.iter().find_map(|option| match option {
+        BitArrayOption::Little { .. } => Some("BitArrayEndianness.Little"),
+        BitArrayOption::Native { .. } => Some("BitArrayEndianness.Native"),
+        BitArrayOption::Big { .. } => Some("BitArrayEndianness.Big"),
+        _ => None,
+    });
+    endianness.unwrap_or("BitArrayEndianness.Big").to_doc()
 }
 
-#[derive(Debug, Clone)]
-enum BitArrayPattern<'a> {
-    Int64 {
-        signed: bool,
-        size: Option<Document<'a>>,
-    },
-    Float {
-        signed: bool,
-        size: Option<Document<'a>>,
-    },
-    Utf8 {
-        endianness: Option<BitArrayEndianness>,
-        size: Option<Document<'a>>,
-    },
-    Utf16 {
-        endianness: Option<BitArrayEndianness>,
-        size: Option<Document<'a>>,
-    },
-    Utf32 {
-        endianness: Option<BitArrayEndianness>,
-        size: Option<Document<'a>>,
-    },
-    Utf8Codepoint {
-        size: Option<Document<'a>>,
-    },
-    Utf16Codepoint {
-        size: Option<Document<'a>>,
-    },
-    Utf32Codepoint {
-        size: Option<Document<'a>>,
-    },
-    Bytes {
-        size: Option<Document<'a>>,
-    },
-    Bits {
-        size: Option<Document<'a>>,
-    },
-    Binding {
-        size: Option<Document<'a>>,
-        signed: Option<bool>,
-    },
+/// Whether a constructor is for a type rendered as an F# record: the type's
+/// only constructor, named after the type, with labels for all its fields
+fn is_record_constructor(
+    name: &EcoString,
+    type_: &Type,
+    variants_count: u16,
+    arity: u16,
+    field_map: &FieldMap,
+) -> bool {
+    let type_name = match type_.return_type() {
+        Some(return_type) => return_type.named_type_name(),
+        None => type_.named_type_name(),
+    };
+    variants_count == 1
+        && arity > 0
+        && arity as usize == field_map.fields.len()
+        && type_name.is_some_and(|(_, type_name)| type_name == *name)
+}
+
+/// The type of a constructor which must be wrapped in a curried function to
+/// be used as a value, and its fields if it is rendered as an F# record
+fn curried_constructor(expr: &TypedExpr) -> Option<(&Type, Option<&FieldMap>)> {
+    let (name, type_, variants_count, arity, field_map) = match expr {
+        TypedExpr::Var {
+            constructor:
+                ValueConstructor {
+                    variant:
+                        ValueConstructorVariant::Record {
+                            name,
+                            variants_count,
+                            arity,
+                            field_map,
+                            ..
+                        },
+                    type_,
+                    ..
+                },
+            ..
+        }
+        | TypedExpr::ModuleSelect {
+            constructor:
+                ModuleValueConstructor::Record {
+                    name,
+                    variants_count,
+                    arity,
+                    field_map,
+                    type_,
+                    ..
+                },
+            ..
+        } => (name, type_, *variants_count, *arity, field_map.as_ref()),
+        _ => return None,
+    };
+
+    match field_map {
+        Some(field_map) if is_record_constructor(name, type_, variants_count, arity, field_map) => {
+            Some((type_, Some(field_map)))
+        }
+        _ if arity > 1 => Some((type_, None)),
+        _ => None,
+    }
 }
 
-impl<'a> BitArrayPattern<'a> {
-    fn size(&self) -> Option<Document<'a>> {
-        match self {
-            BitArrayPattern::Binding { size, .. }
-            | BitArrayPattern::Bytes { size }
-            | BitArrayPattern::Bits { size }
-            | BitArrayPattern::Float { size, .. }
-            | BitArrayPattern::Int64 { size, .. }
-            | BitArrayPattern::Utf8Codepoint { size }
-            | BitArrayPattern::Utf16Codepoint { size }
-            | BitArrayPattern::Utf32Codepoint { size }
-            | BitArrayPattern::Utf8 { size, .. }
-            | BitArrayPattern::Utf16 { size, .. }
-            | BitArrayPattern::Utf32 { size, .. } => size.clone(),
+/// Whether the constructor of a record update is for a type rendered as an F#
+/// record, which can be updated with a copy and update expression
+fn is_record_update_of_record_type(constructor: &TypedExpr) -> bool {
+    match constructor {
+        TypedExpr::Var {
+            constructor:
+                ValueConstructor {
+                    variant:
+                        ValueConstructorVariant::Record {
+                            name,
+                            variants_count,
+                            arity,
+                            field_map: Some(field_map),
+                            ..
+                        },
+                    type_,
+                    ..
+                },
+            ..
         }
+        | TypedExpr::ModuleSelect {
+            constructor:
+                ModuleValueConstructor::Record {
+                    name,
+                    variants_count,
+                    arity,
+                    field_map: Some(field_map),
+                    type_,
+                    ..
+                },
+            ..
+        } => is_record_constructor(name, type_, *variants_count, *arity, field_map),
+        _ => false,
     }
 }
 
-#[derive(Debug, Clone)]
-enum BitArrayEndianness {
-    Big,
-    Little,
-    Native,
+fn is_constructor_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// A value from the prelude referenced with its module or by another name,
+/// qualified with its type where a constructor of the same name in the module
+/// could shadow it
+fn qualified_prelude_value<'a>(name: &EcoString) -> Document<'a> {
+    match name.as_str() {
+        "Nil" => "()".to_doc(),
+        "True" => "true".to_doc(),
+        "False" => "false".to_doc(),
+        "Ok" => "Result.Ok".to_doc(),
+        "Error" => "Result.Error".to_doc(),
+        _ => sanitize_module_name(name).to_doc(),
+    }
 }
@@ -0,0 +1,420 @@
+//! F# requires the keys of a `Dict` or `Set` to support comparison, so any
+//! type parameter which may end up as a key has to be declared with a
+//! `when 'k: comparison` constraint. This works out which type parameters of
+//! the types and functions of a module need one.
+
+use std::{collections::HashMap, ops::Deref, sync::Arc};
+
+use ecow::EcoString;
+use itertools::Itertools;
+
+use crate::{
+    ast::{
+        visit::{self, Visit},
+        Definition, SrcSpan, TypedExpr, TypedFunction, TypedModule,
+    },
+    type_::{
+        ModuleValueConstructor, Type, TypeVar, ValueConstructor, ValueConstructorVariant,
+        PRELUDE_MODULE_NAME,
+    },
+};
+
+#[derive(Debug, Default)]
+pub struct ComparisonConstraints {
+    /// The indices of the parameters of each custom type in the module that
+    /// need to support comparison
+    types: HashMap<EcoString, Vec<usize>>,
+    /// The ids of the type variables in the signature of each function in the
+    /// module that need to support comparison, or `None` if they can't be
+    /// determined
+    functions: HashMap<EcoString, Option<Vec<u64>>>,
+}
+
+impl ComparisonConstraints {
+    pub fn new(module: &TypedModule) -> Self {
+        let mut constraints = Self::default();
+        constraints.infer_types(module);
+        constraints.infer_functions(module);
+        constraints
+    }
+
+    /// The indices of the parameters of a custom type defined in the module
+    /// that need to support comparison
+    pub fn type_parameters(&self, type_name: &str) -> &[usize] {
+        self.types.get(type_name).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// The ids of the type variables in the signature of a function defined in
+    /// the module that need to support comparison. This is `None` when the
+    /// function passes its type parameters to a function or type from another
+    /// module, as the constraints they need aren't known.
+    pub fn function_type_variables(&self, function_name: &str) -> Option<&[u64]> {
+        match self.functions.get(function_name) {
+            Some(ids) => ids.as_deref(),
+            None => Some(&[]),
+        }
+    }
+
+    /// Types may contain each other, so the constraints are worked out again
+    /// until none of them change
+    fn infer_types(&mut self, module: &TypedModule) {
+        let custom_types = module
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::CustomType(custom_type) => Some(custom_type),
+                _ => None,
+            })
+            .collect_vec();
+        let no_signatures = HashMap::new();
+
+        loop {
+            let mut changed = false;
+            for custom_type in &custom_types {
+                let mut key_types = KeyTypes::new(&module.name, self, &no_signatures);
+                for constructor in &custom_type.constructors {
+                    for argument in &constructor.arguments {
+                        key_types.add_type(&argument.type_);
+                    }
+                }
+                let key_type_variables = key_types.key_type_variables();
+                let parameters = custom_type
+                    .typed_parameters
+                    .iter()
+                    .positions(|parameter| {
+                        type_variables(parameter)
+                            .iter()
+                            .any(|id| key_type_variables.contains(id))
+                    })
+                    .collect_vec();
+
+                if parameters.as_slice() != self.type_parameters(&custom_type.name) {
+                    let _ = self.types.insert(custom_type.name.clone(), parameters);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /// Functions may call each other recursively, so the constraints are
+    /// worked out again until none of them change
+    fn infer_functions(&mut self, module: &TypedModule) {
+        let functions = module
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Function(function) => {
+                    let (_, name) = function.name.as_ref()?;
+                    Some((name, function))
+                }
+                _ => None,
+            })
+            .collect_vec();
+
+        let signatures = functions
+            .iter()
+            .map(|(name, function)| {
+                let type_ = Arc::new(Type::Fn {
+                    args: function
+                        .arguments
+                        .iter()
+                        .map(|argument| argument.type_.clone())
+                        .collect(),
+                    retrn: function.return_type.clone(),
+                });
+                ((*name).clone(), type_)
+            })
+            .collect::<HashMap<_, _>>();
+
+        loop {
+            let mut changed = false;
+            for (name, function) in &functions {
+                let type_variables = self.infer_function(&module.name, &signatures, function);
+                if type_variables.as_deref() != self.function_type_variables(name) {
+                    let _ = self.functions.insert((*name).clone(), type_variables);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    fn infer_function(
+        &self,
+        module_name: &EcoString,
+        signatures: &HashMap<EcoString, Arc<Type>>,
+        function: &TypedFunction,
+    ) -> Option<Vec<u64>> {
+        let mut key_types = KeyTypes::new(module_name, self, signatures);
+        let mut signature = vec![];
+        for type_ in function
+            .arguments
+            .iter()
+            .map(|argument| &argument.type_)
+            .chain(std::iter::once(&function.return_type))
+        {
+            key_types.add_type(type_);
+            signature.extend(type_variables(type_));
+        }
+
+        // The constraints of an external function can't be seen, so it is
+        // assumed to only compare the keys of the dicts and sets in its
+        // signature
+        if function.external_fsharp.is_none() {
+            key_types.visit_typed_function(function);
+        }
+
+        let unknown_type_variables = key_types
+            .unknown_types
+            .iter()
+            .flat_map(type_variables)
+            .collect_vec();
+        if signature
+            .iter()
+            .any(|id| unknown_type_variables.contains(id))
+        {
+            return None;
+        }
+
+        let key_type_variables = key_types.key_type_variables();
+        Some(
+            signature
+                .into_iter()
+                .unique()
+                .filter(|id| key_type_variables.contains(id))
+                .collect(),
+        )
+    }
+}
+
+/// Collects the types used as keys in the types of a definition, along with
+/// the types whose constraints can't be known as they involve definitions
+/// from other modules
+struct KeyTypes<'a> {
+    module_name: &'a EcoString,
+    constraints: &'a ComparisonConstraints,
+    /// The types of the functions of the module, as they were generalised
+    signatures: &'a HashMap<EcoString, Arc<Type>>,
+    key_types: Vec<Arc<Type>>,
+    unknown_types: Vec<Arc<Type>>,
+}
+
+impl<'a> KeyTypes<'a> {
+    fn new(
+        module_name: &'a EcoString,
+        constraints: &'a ComparisonConstraints,
+        signatures: &'a HashMap<EcoString, Arc<Type>>,
+    ) -> Self {
+        Self {
+            module_name,
+            constraints,
+            signatures,
+            key_types: vec![],
+            unknown_types: vec![],
+        }
+    }
+
+    fn key_type_variables(&self) -> Vec<u64> {
+        self.key_types.iter().flat_map(type_variables).collect()
+    }
+
+    fn add_type(&mut self, type_: &Arc<Type>) {
+        match type_.as_ref() {
+            Type::Var { type_ } => match type_.borrow().deref() {
+                TypeVar::Link { type_ } => self.add_type(type_),
+                TypeVar::Unbound { .. } | TypeVar::Generic { .. } => {}
+            },
+            Type::Named {
+                module, name, args, ..
+            } => {
+                if is_comparison_collection(module) {
+                    self.key_types.extend(args.first().cloned());
+                } else if module == self.module_name {
+                    let key_types = self
+                        .constraints
+                        .type_parameters(name)
+                        .iter()
+                        .filter_map(|index| args.get(*index).cloned());
+                    self.key_types.extend(key_types);
+                } else if module != PRELUDE_MODULE_NAME {
+                    self.unknown_types.extend(args.iter().cloned());
+                }
+                for arg in args {
+                    self.add_type(arg);
+                }
+            }
+            Type::Tuple { elems } => {
+                for elem in elems {
+                    self.add_type(elem);
+                }
+            }
+            Type::Fn { args, retrn } => {
+                for arg in args {
+                    self.add_type(arg);
+                }
+                self.add_type(retrn);
+            }
+        }
+    }
+
+    /// A reference to a function, with the type it is used at
+    fn add_function(
+        &mut self,
+        module: &EcoString,
+        name: &EcoString,
+        is_external: bool,
+        type_: &Arc<Type>,
+    ) {
+        if module != self.module_name {
+            // The functions for dicts and sets only compare their keys
+            if !is_external && !is_comparison_collection(module) {
+                self.unknown_types.push(type_.clone());
+            }
+            return;
+        }
+
+        let Some(signature) = self.signatures.get(name) else {
+            return;
+        };
+        match self.constraints.function_type_variables(name) {
+            Some(ids) => {
+                let mut instances = HashMap::new();
+                match_type_variables(signature, type_, &mut instances);
+                let key_types = ids.iter().filter_map(|id| instances.get(id).cloned());
+                self.key_types.extend(key_types);
+            }
+            None => self.unknown_types.push(type_.clone()),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for KeyTypes<'_> {
+    fn visit_typed_expr(&mut self, expr: &'ast TypedExpr) {
+        self.add_type(&expr.type_());
+        visit::visit_typed_expr(self, expr);
+    }
+
+    fn visit_typed_expr_var(
+        &mut self,
+        _location: &'ast SrcSpan,
+        constructor: &'ast ValueConstructor,
+        _name: &'ast EcoString,
+    ) {
+        if let ValueConstructorVariant::ModuleFn {
+            module,
+            name,
+            external_fsharp,
+            ..
+        } = &constructor.variant
+        {
+            self.add_function(module, name, external_fsharp.is_some(), &constructor.type_);
+        }
+    }
+
+    fn visit_typed_expr_module_select(
+        &mut self,
+        _location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        _label: &'ast EcoString,
+        _module_name: &'ast EcoString,
+        _module_alias: &'ast EcoString,
+        constructor: &'ast ModuleValueConstructor,
+    ) {
+        if let ModuleValueConstructor::Fn {
+            module,
+            name,
+            external_fsharp,
+            ..
+        } = constructor
+        {
+            self.add_function(module, name, external_fsharp.is_some(), type_);
+        }
+    }
+}
+
+fn is_comparison_collection(module: &str) -> bool {
+    matches!(module, "gleam/dict" | "gleam/set")
+}
+
+/// The ids of the type variables in a type
+fn type_variables(type_: &Arc<Type>) -> Vec<u64> {
+    fn collect(type_: &Type, ids: &mut Vec<u64>) {
+        match type_ {
+            Type::Var { type_ } => match type_.borrow().deref() {
+                TypeVar::Link { type_ } => collect(type_, ids),
+                TypeVar::Unbound { id } | TypeVar::Generic { id } => ids.push(*id),
+            },
+            Type::Named { args, .. } => args.iter().for_each(|arg| collect(arg, ids)),
+            Type::Tuple { elems } => elems.iter().for_each(|elem| collect(elem, ids)),
+            Type::Fn { args, retrn } => {
+                args.iter().for_each(|arg| collect(arg, ids));
+                collect(retrn, ids);
+            }
+        }
+    }
+
+    let mut ids = vec![];
+    collect(type_, &mut ids);
+    ids
+}
+
+/// Records the types that the type variables of a generic type were
+/// instantiated with
+fn match_type_variables(
+    generic: &Arc<Type>,
+    instance: &Arc<Type>,
+    instances: &mut HashMap<u64, Arc<Type>>,
+) {
+    if let Type::Var { type_ } = instance.as_ref() {
+        if let TypeVar::Link { type_ } = type_.borrow().deref() {
+            return match_type_variables(generic, type_, instances);
+        }
+    }
+
+    match generic.as_ref() {
+        Type::Var { type_ } => match type_.borrow().deref() {
+            TypeVar::Link { type_ } => match_type_variables(type_, instance, instances),
+            TypeVar::Unbound { id } | TypeVar::Generic { id } => {
+                let _ = instances.insert(*id, instance.clone());
+            }
+        },
+        Type::Named { args, .. } => {
+            if let Type::Named {
+                args: instance_args,
+                ..
+            } = instance.as_ref()
+            {
+                for (arg, instance_arg) in args.iter().zip(instance_args) {
+                    match_type_variables(arg, instance_arg, instances);
+                }
+            }
+        }
+        Type::Tuple { elems } => {
+            if let Type::Tuple {
+                elems: instance_elems,
+            } = instance.as_ref()
+            {
+                for (elem, instance_elem) in elems.iter().zip(instance_elems) {
+                    match_type_variables(elem, instance_elem, instances);
+                }
+            }
+        }
+        Type::Fn { args, retrn } => {
+            if let Type::Fn {
+                args: instance_args,
+                retrn: instance_retrn,
+            } = instance.as_ref()
+            {
+                for (arg, instance_arg) in args.iter().zip(instance_args) {
+                    match_type_variables(arg, instance_arg, instances);
+                }
+                match_type_variables(retrn, instance_retrn, instances);
+            }
+        }
+    }
+}
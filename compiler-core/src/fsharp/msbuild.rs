@@ -0,0 +1,169 @@
+//! Parsing of the errors reported by `dotnet build`, so that they can be shown
+//! as Gleam diagnostics.
+
+use std::sync::OnceLock;
+
+use camino::Utf8PathBuf;
+use ecow::EcoString;
+use regex::Regex;
+
+use crate::{ast::SrcSpan, line_numbers::LineNumbers};
+
+/// An error reported by the F# compiler, located in the Gleam source if it
+/// came from a `#line` directive in the generated code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildError {
+    pub path: Utf8PathBuf,
+    /// 1-indexed
+    pub line: u32,
+    /// 1-indexed
+    pub column: u32,
+    pub code: EcoString,
+    pub message: String,
+    /// The Gleam source and the span of the line the error refers to, if the
+    /// error is in a Gleam file
+    pub source: Option<(EcoString, SrcSpan)>,
+}
+
+impl BuildError {
+    /// Locates the error in the given source, pointing at the whole line as
+    /// the column is for the generated F#
+    pub fn with_source(mut self, src: EcoString) -> Self {
+        let line_numbers = LineNumbers::new(&src);
+        let start = line_numbers.byte_index(self.line.saturating_sub(1), 0);
+        let end = line_numbers.byte_index(self.line, 0);
+        let text = &src[start as usize..end as usize];
+        let leading = text.len() - text.trim_start().len();
+        let span = SrcSpan::new(start + leading as u32, start + text.trim_end().len() as u32);
+        self.source = Some((src, span));
+        self
+    }
+}
+
+fn error_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(
+            r"^\s*(?P<path>.+?)\((?P<line>\d+),(?P<column>\d+)(?:,\d+,\d+)?\): error (?P<code>[A-Z]+\d+): (?P<message>.*)$",
+        )
+        .expect("MSBuild error regex")
+    })
+}
+
+/// Parses the errors from an MSBuild log. Messages may continue over several
+/// lines, and MSBuild appends the project being built to the last of them.
+/// Duplicate errors, which MSBuild reports again in its summary, are removed.
+pub fn parse_errors(log: &str) -> Vec<BuildError> {
+    let mut errors: Vec<BuildError> = vec![];
+    let mut current: Option<BuildError> = None;
+
+    for line in log.lines() {
+        if let Some(captures) = error_regex().captures(line) {
+            errors.extend(current.take());
+            current = Some(BuildError {
+                path: Utf8PathBuf::from(&captures["path"]),
+                line: captures["line"].parse().unwrap_or(1),
+                column: captures["column"].parse().unwrap_or(1),
+                code: captures["code"].into(),
+                message: captures["message"].into(),
+                source: None,
+            });
+        } else if let Some(error) = current.as_mut() {
+            error.message.push('\n');
+            error.message.push_str(line);
+        } else {
+            continue;
+        }
+
+        if let Some(error) = current.as_mut() {
+            if let Some(message) = strip_project(&error.message) {
+                error.message = message.trim_end().into();
+                errors.extend(current.take());
+            }
+        }
+    }
+    errors.extend(current);
+
+    let mut unique: Vec<BuildError> = vec![];
+    for error in errors {
+        if !unique.contains(&error) {
+            unique.push(error);
+        }
+    }
+    unique
+}
+
+/// The message without the ` [project.fsproj]` suffix, if it has one
+fn strip_project(message: &str) -> Option<&str> {
+    if !message.ends_with("proj]") {
+        return None;
+    }
+    message.rfind(" [").map(|index| &message[..index])
+}
+
+#[test]
+fn parse_single_line_error() {
+    let log = "/app/src/wibble.gleam(3,5): error FS0039: The value or constructor 'wobble' is not defined. [/app/build/wibble.fsproj]\n";
+    assert_eq!(
+        parse_errors(log),
+        vec![BuildError {
+            path: "/app/src/wibble.gleam".into(),
+            line: 3,
+            column: 5,
+            code: "FS0039".into(),
+            message: "The value or constructor 'wobble' is not defined.".into(),
+            source: None,
+        }]
+    );
+}
+
+#[test]
+fn parse_multi_line_error() {
+    let log = r#"/app/src/wibble.gleam(10,12,10,20): error FS0001: This expression was expected to have type
+    'int64'
+but here has type
+    'string'     [/app/build/wibble.fsproj]
+/app/build/gleam_prelude.fs(1,1): error FS0222: Files in libraries must begin with a namespace. [/app/build/wibble.fsproj]
+"#;
+    let errors = parse_errors(log);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].line, 10);
+    assert_eq!(errors[0].column, 12);
+    assert_eq!(
+        errors[0].message,
+        "This expression was expected to have type\n    'int64'\nbut here has type\n    'string'"
+    );
+    assert_eq!(errors[1].path, "/app/build/gleam_prelude.fs");
+    assert_eq!(errors[1].code, "FS0222");
+}
+
+#[test]
+fn parse_ignores_other_output_and_duplicates() {
+    let log = r#"  Determining projects to restore...
+/app/src/wibble.gleam(3,5): error FS0039: Not defined. [/app/build/wibble.fsproj]
+
+Build FAILED.
+
+/app/src/wibble.gleam(3,5): error FS0039: Not defined. [/app/build/wibble.fsproj]
+    0 Warning(s)
+    1 Error(s)
+"#;
+    assert_eq!(parse_errors(log).len(), 1);
+}
+
+#[test]
+fn locate_error_in_gleam_source() {
+    let error = BuildError {
+        path: "/app/src/wibble.gleam".into(),
+        line: 2,
+        column: 9,
+        code: "FS0039".into(),
+        message: "Not defined.".into(),
+        source: None,
+    }
+    .with_source("pub fn main() {\n  wobble(1)  \n}\n".into());
+    assert_eq!(
+        error.source.map(|(_, span)| span),
+        Some(SrcSpan::new(18, 27))
+    );
+}
@@ -26,22 +26,113 @@ type EmptyTuple = EmptyTuple
 type UtfCodepoint = UtfCodepoint of System.Text.Rune
 
 
+/// Identities used to order values which are neither equal nor comparable
+/// structurally. Values are held weakly, so this doesn't keep them alive.
+module private Identity =
+    let private identities = Runtime.CompilerServices.ConditionalWeakTable<obj, obj>()
+    let mutable private next = 0L
+
+    let get (value: obj) : int64 =
+        identities.GetValue(value, fun _ -> box (Threading.Interlocked.Increment(&next)))
+        |> unbox
+
+/// A value of any type. Equality and hashing are structural over the wrapped
+/// value, so two `Dynamic`s are equal when Gleam's `==` would consider the
+/// values they hold equal.
+///
+/// Comparison is a total order: values are first ordered by kind
+/// (`Nil` < `Bool` < numbers < `String` < everything else), numbers are
+/// compared by value with an `Int` ordered before an equal `Float`, and other
+/// values are ordered by type name and then structurally. Values which cannot
+/// be compared structurally, such as functions, are ordered by hash code and
+/// then by an identity assigned when first compared, so distinct values never
+/// compare as equal.
 [<CustomEquality; CustomComparison>]
 type Dynamic =
     private
     | Dynamic of obj
 
-    interface System.IComparable with
+    static member private Rank(value: obj) =
+        match value with
+        | null -> 0
+        | :? bool -> 1
+        | :? int64
+        | :? float -> 2
+        | :? string -> 3
+        | _ -> 4
+
+    /// Union cases with fields are compiled to subclasses of the union type,
+    /// so values of the same union are compared using their declaring type.
+    static member private TypeOf(value: obj) =
+        let t = value.GetType()
+
+        if
+            not (isNull t.BaseType)
+            && Microsoft.FSharp.Reflection.FSharpType.IsUnion(t.BaseType, true)
+        then
+            t.BaseType
+        else
+            t
+
+    static member private CompareValues(a: obj, b: obj) : int =
+        match a, b with
+        | null, null -> 0
+        | (:? bool as a), (:? bool as b) -> compare a b
+        | (:? string as a), (:? string as b) -> String.CompareOrdinal(a, b) |> sign
+        | (:? int64 as a), (:? int64 as b) -> compare a b
+        | (:? float as a), (:? float as b) -> a.CompareTo(b)
+        | (:? int64 as a), (:? float as b) ->
+            match (float a).CompareTo(b) with
+            | 0 -> -1
+            | c -> c
+        | (:? float as a), (:? int64 as b) ->
+            match a.CompareTo(float b) with
+            | 0 -> 1
+            | c -> c
+        | a, b when Dynamic.Rank a <> Dynamic.Rank b -> compare (Dynamic.Rank a) (Dynamic.Rank b)
+        | a, b ->
+            let typeA = Dynamic.TypeOf a
+            let typeB = Dynamic.TypeOf b
+
+            if typeA <> typeB then
+                String.CompareOrdinal(typeA.FullName, typeB.FullName) |> sign
+            elif LanguagePrimitives.GenericEqualityER a b then
+                0
+            else
+                let structural =
+                    match a with
+                    | :? IComparable ->
+                        try
+                            Some(LanguagePrimitives.GenericComparison a b)
+                        with :? ArgumentException ->
+                            None
+                    | _ -> None
+
+                match structural with
+                | Some c when c <> 0 -> sign c
+                | _ ->
+                    match compare (LanguagePrimitives.GenericHash a) (LanguagePrimitives.GenericHash b) with
+                    | 0 -> compare (Identity.get a) (Identity.get b)
+                    | c -> c
+
+    interface IComparable with
         member this.CompareTo(obj) =
-            System.StringComparer.InvariantCulture.Compare($"%A{this}", $"%A{obj}")
+            match this, obj with
+            | Dynamic a, (:? Dynamic as other) ->
+                let (Dynamic b) = other
+                Dynamic.CompareValues(a, b)
+            | _ -> invalidArg "obj" "Cannot compare Dynamic with non-Dynamic object"
 
     override this.Equals(obj) =
-        match obj with
-        | :? Dynamic as dynamic -> System.StringComparer.InvariantCulture.Equals($"%A{this}", $"%A{dynamic}")
+        match this, obj with
+        | Dynamic a, (:? Dynamic as other) ->
+            let (Dynamic b) = other
+            LanguagePrimitives.GenericEqualityER a b
         | _ -> false
 
     override this.GetHashCode() =
-        System.StringComparer.InvariantCulture.GetHashCode($"%A{this}")
+        let (Dynamic value) = this
+        LanguagePrimitives.GenericHash value
 
     static member From(a: obj) : Dynamic =
         match a with
@@ -103,8 +194,11 @@ type Uri = {
 //     | Size of int64
 //     | Unit of int64
 
+/// A sequence of bits, stored most significant bit first. The size in bits
+/// need not be a multiple of 8, in which case the unused low bits of the last
+/// byte are always zero.
 [<Struct; CustomEquality; CustomComparison>]
-type BitArray private (_buffer: byte[]) =
+type BitArray internal (_buffer: byte[], _bitSize: int64) =
     static let b64EncodeLookup = [
         65uy
         66uy
@@ -172,8 +266,29 @@ type BitArray private (_buffer: byte[]) =
         47uy
     ]
 
+    internal new(buffer: byte[]) =
+        BitArray(buffer, (if isNull buffer then 0L else buffer.LongLength * 8L))
+
     member internal this.Buffer = if isNull _buffer then Array.empty else _buffer
 
+    member this.BitSize = _bitSize
+
+    member this.IsByteAligned = _bitSize % 8L = 0L
+
+    /// Whether the bit at the given index is set, counting from the most
+    /// significant bit of the first byte
+    member internal this.Bit(index: int64) =
+        (this.Buffer[int (index / 8L)] >>> (7 - int (index % 8L))) &&& 1uy = 1uy
+
+    /// A copy of the given range of bits, or `None` if it is out of bounds
+    member this.SliceBits(start: int64, bitSize: int64) : BitArray option =
+        if start < 0L || bitSize < 0L || start + bitSize > this.BitSize then
+            None
+        else
+            let builder = BitArrayBuilder()
+            builder.AppendBits(this, start, bitSize)
+            Some(builder.ToBitArray())
+
     static member Empty = BitArray()
 
     member this.FloatFromSlice(start: int64, end': int64) =
@@ -208,11 +323,15 @@ type BitArray private (_buffer: byte[]) =
 
     override this.Equals(obj) =
         match obj with
-        | :? BitArray as other when other.Length = this.Length ->
+        | :? BitArray as other when other.BitSize = this.BitSize ->
             System.Linq.Enumerable.SequenceEqual(this.Buffer, other.Buffer)
         | _ -> false
 
-    override this.GetHashCode() = System.HashCode.Combine(this.Buffer)
+    override this.GetHashCode() =
+        let mutable hash = System.HashCode()
+        hash.Add(this.BitSize)
+        hash.AddBytes(ReadOnlySpan(this.Buffer))
+        hash.ToHashCode()
 
     member this.ByteAt(index: int64) = this.Buffer.[int index]
 
@@ -235,7 +354,7 @@ type BitArray private (_buffer: byte[]) =
             Ok(slice)
 
     member this.Slice(start: int64, length: int64) : Result<BitArray, unit> =
-        this.SliceBuffer(start, length) |> Result.map BitArray
+        this.SliceBuffer(start, length) |> Result.map BitArray.FromBytes
 
     member this.SliceAfter(index: int64) =
         let index = int index
@@ -334,8 +453,15 @@ type BitArray private (_buffer: byte[]) =
         with e ->
             Error()
 
+    /// Bit arrays are ordered bit by bit, with a bit array ordered before any
+    /// longer bit array that it is a prefix of
+    member private this.CompareBits(other: BitArray) =
+        let bytes = ReadOnlySpan(this.Buffer).SequenceCompareTo(ReadOnlySpan(other.Buffer))
+
+        if bytes <> 0 then sign bytes else compare this.BitSize other.BitSize
+
     member this.Compare(obj: BitArray) : Order =
-        let comp = compare this.Buffer obj.Buffer
+        let comp = this.CompareBits(obj)
 
         if comp = 0 then Eq
         elif comp < 0 then Lt
@@ -344,7 +470,7 @@ type BitArray private (_buffer: byte[]) =
     interface IComparable with
         member this.CompareTo(obj) =
             match obj with
-            | :? BitArray as other -> compare this.Buffer other.Buffer
+            | :? BitArray as other -> this.CompareBits(other)
             | _ -> invalidArg "obj" "Cannot compare BitArray with non-BitArray object"
 
     member this.MatchSegments([<ParamArray>] matchSegments: BitArraySegment[]) =
@@ -387,43 +513,106 @@ type BitArray private (_buffer: byte[]) =
         BitArray(System.BitConverter.GetBytes(f))
 
     static member Concat(bit_arrays: BitArray seq) =
-        let buffer = Array.concat [ for ba in bit_arrays -> ba.Buffer ]
-        BitArray(buffer)
+        let builder = BitArrayBuilder()
 
-    static member Create([<ParamArray>] segments: BitArraySegment[]) =
-        let size segment =
-            match segment.size with
-            | Some size -> int size
-            | None -> segment.Length
+        for bits in bit_arrays do
+            builder.AppendBits(bits, 0L, bits.BitSize)
 
-        let byte_length = segments |> Array.map size |> Array.sum
+        builder.ToBitArray()
 
-        let mutable buffer = Array.zeroCreate byte_length
-        let mutable cursor = 0
+    static member Create([<ParamArray>] segments: BitArraySegment[]) =
+        let builder = BitArrayBuilder()
 
         for segment in segments do
+            segment.AppendTo(builder)
 
-            let valueBuffer = segment.ToBytes()
+        builder.ToBitArray()
 
-            System.Buffer.BlockCopy(valueBuffer, 0, buffer, cursor, valueBuffer.Length)
-            cursor <- cursor + valueBuffer.Length
+    /// Formats the bit array as Erlang does, with the size of any trailing
+    /// partial byte, e.g. `<<1, 2:3>>`
+    override this.ToString() =
+        // Struct members can't capture `this` in a closure
+        let buffer, bitSize = this.Buffer, this.BitSize
 
-        BitArray(buffer)
+        let bytes = [
+            for index in 0 .. buffer.Length - 1 do
+                let remaining = bitSize - int64 index * 8L
+                let byte = int buffer[index]
 
-    override this.ToString() =
-        let builder = System.Text.StringBuilder()
-        builder.Append("<<") |> ignore
-        let mutable i = 0
+                if remaining >= 8L then
+                    string byte
+                else
+                    $"{byte >>> (8 - int remaining)}:{remaining}"
+        ]
+
+        "<<" + String.Join(", ", bytes) + ">>"
+
+/// Builds a bit array from values of any number of bits
+and internal BitArrayBuilder() =
+    let bytes = ResizeArray<byte>()
+    let mutable bitSize = 0L
 
-        for b in this.Buffer do
-            builder.Append(sprintf "%i" b) |> ignore
+    member _.AppendBit(bit: bool) =
+        let offset = int (bitSize % 8L)
 
-            if i + 1 < this.Buffer.Length then
-                builder.Append(", ") |> ignore
+        if offset = 0 then
+            bytes.Add(0uy)
 
-            i <- i + 1
+        if bit then
+            bytes[bytes.Count - 1] <- bytes[bytes.Count - 1] ||| (0x80uy >>> offset)
 
-        builder.Append(">>") |> string
+        bitSize <- bitSize + 1L
+
+    member this.AppendBytes(source: byte[]) =
+        if bitSize % 8L = 0L then
+            bytes.AddRange(source)
+            bitSize <- bitSize + source.LongLength * 8L
+        else
+            for byte in source do
+                for index in 7 .. -1 .. 0 do
+                    this.AppendBit((byte >>> index) &&& 1uy = 1uy)
+
+    member this.AppendBits(source: BitArray, start: int64, count: int64) =
+        if count > 0L && (start < 0L || start + count > source.BitSize) then
+            invalidArg "count" $"Cannot take {count} bits from a bit array of {source.BitSize} bits"
+
+        // Whole bytes can be copied directly when both sides are byte aligned
+        let copied =
+            if bitSize % 8L = 0L && start % 8L = 0L then
+                let first = int (start / 8L)
+                let wholeBytes = int (count / 8L)
+                bytes.AddRange(ArraySegment(source.Buffer, first, wholeBytes))
+                bitSize <- bitSize + int64 wholeBytes * 8L
+                int64 wholeBytes * 8L
+            else
+                0L
+
+        for index in start + copied .. start + count - 1L do
+            this.AppendBit(source.Bit index)
+
+    /// Appends the two's complement representation of the integer in the given
+    /// number of bits. As on Erlang, a little endian integer whose size is not
+    /// a multiple of 8 has its whole bytes first, followed by the remaining
+    /// most significant bits.
+    member this.AppendInt(value: int64, size: int64, endianness: BitArrayEndianness) =
+        // Bits above the 64 of the value are copies of its sign bit
+        let bit index =
+            if index >= 64L then value < 0L else (value >>> int index) &&& 1L = 1L
+
+        if endianness.IsLittleEndian then
+            let wholeBytes = size / 8L
+
+            for byteIndex in 0L .. wholeBytes - 1L do
+                for index in 7L .. -1L .. 0L do
+                    this.AppendBit(bit (byteIndex * 8L + index))
+
+            for index in size - 1L .. -1L .. wholeBytes * 8L do
+                this.AppendBit(bit index)
+        else
+            for index in size - 1L .. -1L .. 0L do
+                this.AppendBit(bit index)
+
+    member _.ToBitArray() = BitArray(bytes.ToArray(), bitSize)
 
 // Patterns
 
@@ -434,11 +623,11 @@ and [<Struct>] BitArraySegmentValue =
     | Int of int: int64
     | Byte of byte: byte
     | Utf8 of utf8: byte[]
-    | Utf16 of utf16: byte[]
-    | Utf32 of utf32: byte[]
+    | Utf16 of utf16: string
+    | Utf32 of utf32: string
     | Utf8Codepoint of utf8Codepoint: UtfCodepoint
-    | Utf16Codepoint of utf16Codepoint: byte[]
-    | Utf32Codepoint of utf32Codepoint: byte[]
+    | Utf16Codepoint of utf16Codepoint: UtfCodepoint
+    | Utf32Codepoint of utf32Codepoint: UtfCodepoint
 
     static member inline SizeOf(str: string) =
         System.Text.Encoding.Unicode.GetBytes(str).Length
@@ -458,14 +647,30 @@ and [<Struct>] BitArraySegmentValue =
 
     static member inline SizeOf(UtfCodepoint utf8Codepoint) = utf8Codepoint.Utf8SequenceLength
 
-    static member FromString(str: string) =
-        BitArraySegmentValue.Utf16(System.Text.Encoding.Unicode.GetBytes(str))
+    static member FromString(str: string) = BitArraySegmentValue.Utf16(str)
 
 and [<Struct>] BitArrayEndianness =
     | Big
     | Little
     | Native
 
+    member this.IsLittleEndian =
+        match this with
+        | Big -> false
+        | Little -> true
+        | Native -> BitConverter.IsLittleEndian
+
+    /// The UTF-16 encoding with this byte order, without a byte order mark
+    member this.Utf16: Text.Encoding =
+        if this.IsLittleEndian then
+            Text.Encoding.Unicode
+        else
+            Text.Encoding.BigEndianUnicode
+
+    /// The UTF-32 encoding with this byte order, without a byte order mark
+    member this.Utf32: Text.Encoding =
+        Text.UTF32Encoding(not this.IsLittleEndian, false)
+
 and [<Struct>] BitArraySegment = {
     endianness: BitArrayEndianness option
     size: int64 option
@@ -482,25 +687,44 @@ and [<Struct>] BitArraySegment = {
         value = Bytes Array.empty
     }
 
-    member this.Length =
+    /// The number of bits given by the segment's size and unit, if it has a size
+    member this.BitSize =
+        let unit = defaultArg this.unit 1L
+        this.size |> Option.map (fun size -> size * unit)
+
+    member internal this.AppendTo(builder: BitArrayBuilder) =
+        let endianness = defaultArg this.endianness BitArrayEndianness.Big
+
         match this.value with
-        | Bytes bytes
-        | Utf8 bytes
-        | Utf16 bytes
-        | Utf32 bytes -> bytes.Length
-        | Utf8Codepoint(UtfCodepoint c) -> c.Utf8SequenceLength
-        | Utf16Codepoint _ -> 16
-        | Utf32Codepoint _ -> 32
-        | Byte _ -> sizeof<byte>
-        | Int i when i <= int64 Byte.MaxValue -> sizeof<byte>
-        | Int _ when this.signed = Some true -> sizeof<int64>
-        | Int _ -> sizeof<uint64>
-        | Float _ -> sizeof<double>
-        | Bits b -> b.Buffer.Length
+        | Int i -> builder.AppendInt(i, defaultArg this.BitSize 8L, endianness)
+        | Byte b -> builder.AppendInt(int64 b, defaultArg this.BitSize 8L, endianness)
+        | Float f ->
+            let bytes =
+                match defaultArg this.BitSize 64L with
+                | 64L -> BitConverter.GetBytes(f)
+                | 32L -> BitConverter.GetBytes(float32 f)
+                | 16L -> BitConverter.GetBytes(Half.op_Explicit f: Half)
+                | size -> invalidArg "size" $"Floats must be 16, 32 or 64 bits, got {size}"
+
+            let bytes =
+                if endianness.IsLittleEndian = BitConverter.IsLittleEndian then
+                    bytes
+                else
+                    Array.rev bytes
+
+            builder.AppendBytes(bytes)
+        | Bits bits -> builder.AppendBits(bits, 0L, defaultArg this.BitSize bits.BitSize)
+        | Bytes bytes ->
+            let bits = BitArray(bytes)
+            builder.AppendBits(bits, 0L, defaultArg this.BitSize bits.BitSize)
+        | _ -> builder.AppendBytes(this.ToBytes())
 
     member this.Equals(bytes: byte[]) = this.ToBytes() = bytes
 
     member this.ToBytes() =
+        // UTF-16 and UTF-32 are big endian unless the segment says otherwise
+        let endianness = defaultArg this.endianness BitArrayEndianness.Big
+
         match this.value with
         | Bits ba -> ba.Buffer
         | Bytes bytes -> bytes
@@ -510,11 +734,11 @@ and [<Struct>] BitArraySegment = {
         | Int i when this.signed = Some true -> System.BitConverter.GetBytes(uint64 i)
         | Int i -> System.BitConverter.GetBytes(i)
         | Utf8Codepoint(UtfCodepoint(cp: Text.Rune)) -> System.Text.Encoding.UTF8.GetBytes(string cp)
-        | Utf8 bytes
-        | Utf16 bytes
-        | Utf32 bytes
-        | Utf16Codepoint bytes
-        | Utf32Codepoint bytes -> bytes
+        | Utf8 bytes -> bytes
+        | Utf16 str -> endianness.Utf16.GetBytes(str)
+        | Utf32 str -> endianness.Utf32.GetBytes(str)
+        | Utf16Codepoint(UtfCodepoint cp) -> endianness.Utf16.GetBytes(string cp)
+        | Utf32Codepoint(UtfCodepoint cp) -> endianness.Utf32.GetBytes(string cp)
 
     static member FromInt64(i: int64) = {
         BitArraySegment.Empty with
@@ -541,6 +765,28 @@ and [<Struct>] BitArraySegmentMatch = {
     value: BitArraySegmentValue
 }
 
+/// The base type of the exceptions raised by `panic`, `todo` and failed
+/// `let assert` expressions, with the location of the expression in the
+/// Gleam source code
+type GleamError(message: string, moduleName: string, functionName: string, line: int) =
+    inherit Exception(message)
+    member _.Module = moduleName
+    member _.Function = functionName
+    member _.Line = line
+
+/// Raised by a `panic` expression
+type GleamPanic(message: string, moduleName: string, functionName: string, line: int) =
+    inherit GleamError(message, moduleName, functionName, line)
+
+/// Raised by a `todo` expression
+type GleamTodo(message: string, moduleName: string, functionName: string, line: int) =
+    inherit GleamError(message, moduleName, functionName, line)
+
+/// Raised when the pattern of a `let assert` does not match the value
+type GleamAssertError(message: string, moduleName: string, functionName: string, line: int, value: obj) =
+    inherit GleamError(message, moduleName, functionName, line)
+    member _.Value = value
+
 [<AutoOpen>]
 module Prelude =
     /// Split a string into a prefix and a suffix
@@ -555,162 +801,197 @@ module Prelude =
     let (|Tuple1|) (t: System.Tuple<'a>) = t.Item1
     let Tuple1 (a: 'a) = System.Tuple.Create(a)
 
+    /// Match a `bigint` against an integer literal, which F# does not allow in patterns directly
+    let (|Gleam_codegen_bigint|_|) (expected: string) (value: bigint) =
+        if value = bigint.Parse(expected) then Some() else None
 
-    let (|BitArraySegments|_|) (segments: BitArraySegment[]) (bitArray: BitArray) =
-        //
-        bitArray.MatchSegments(segments)
+    // The integer helpers are inline so they work for both `int64` and `bigint` representations
 
-module BitArray =
-    module Segment =
+    /// Integer division, where dividing by zero returns zero as it does on the other Gleam targets
+    let inline divideInt (a: ^a) (b: ^a) : ^a =
+        if b = LanguagePrimitives.GenericZero then LanguagePrimitives.GenericZero
+        // Int64.MinValue / -1 would throw an OverflowException
+        elif b = -LanguagePrimitives.GenericOne then -a
+        else a / b
 
-        let (|Int64|_|) (bytes: byte[]) =
-            if bytes.Length = sizeof<int64> then
-                System.BitConverter.ToInt64(bytes, 0) |> Some
-            else
-                None
-
-        let (|Int64BigEndian|_|) (bytes: byte[]) =
-            if System.BitConverter.IsLittleEndian then
-                System.BitConverter.ToInt64(bytes |> Array.rev, 0) |> Some
-            elif bytes.Length = sizeof<int64> then
-                System.BitConverter.ToInt64(bytes, 0) |> Some
-            else
-                None
+    /// Integer remainder, where a divisor of zero returns zero as it does on the other Gleam targets
+    let inline remainderInt (a: ^a) (b: ^a) : ^a =
+        if b = LanguagePrimitives.GenericZero || b = -LanguagePrimitives.GenericOne then
+            LanguagePrimitives.GenericZero
+        else
+            a % b
 
-        let (|Int64LittleEndian|_|) (bytes: byte[]) =
-            if System.BitConverter.IsLittleEndian then
-                if bytes.Length = sizeof<int64> then
-                    System.BitConverter.ToInt64(bytes, 0) |> Some
-                else
-                    System.BitConverter.ToInt64(bytes |> Array.rev, 0) |> Some
-            else
-                None
+    /// Float division, where dividing by zero returns zero rather than infinity or NaN
+    let divideFloat (a: float) (b: float) : float = if b = 0.0 then 0.0 else a / b
 
-        let (|UInt64|_|) (bytes: byte[]) =
-            if bytes.Length = sizeof<uint64> then
-                System.BitConverter.ToUInt64(bytes, 0) |> Some
-            else
-                None
+    /// The value of an `int` bit array segment when Gleam `Int`s are `bigint`s.
+    /// As on Erlang only the low bits of the value are kept, so it is truncated
+    /// to 64 bits rather than overflowing.
+    let bigintSegmentValue (value: bigint) : int64 =
+        int64 (uint64 (value &&& bigint UInt64.MaxValue))
 
-        let (|Float64|_|) (bytes: byte[]) =
-            if bytes.Length = sizeof<double> then
-                System.BitConverter.ToDouble(bytes, 0) |> Some
-            else
-                None
+    /// The size of a bit array segment when Gleam `Int`s are `bigint`s
+    let bigintSegmentSize (size: bigint) : int64 =
+        if size < bigint Int64.MinValue || size > bigint Int64.MaxValue then
+            invalidArg "size" $"Bit array segment size {size} is too large"
+        else
+            int64 size
 
-        let (|Byte|_|) (bytes: byte[]) =
-            if bytes.Length = sizeof<byte> then Some bytes.[0] else None
 
-        let (|Bits|_|) (bytes: byte[]) =
-            if bytes.Length = 0 then
-                None
-            else
-                BitArray.FromBytes(bytes) |> Some
+    let (|BitArraySegments|_|) (segments: BitArraySegment[]) (bitArray: BitArray) =
+        //
+        bitArray.MatchSegments(segments)
 
-        let (|SizedBits|_|) (size: int64) (bytes: byte[]) =
-            if bytes.LongLength = size then
-                None
-            else
-                BitArray.FromBytes(bytes) |> Some
+/// Active patterns used to match bit array patterns. Each segment pattern
+/// matches the start of a bit array and returns the bits after the segment,
+/// so a Gleam bit array pattern becomes a chain of these patterns ending in
+/// `Empty` or a binding for the rest of the bit array. Sizes are in bits.
+module BitArray =
 
-        let (|Utf8String|_|) (bytes: byte[]) =
-            if bytes.Length = 0 then
-                None
+    let private splitAt (bits: int64) (bitArray: BitArray) =
+        match bitArray.SliceBits(0L, bits) with
+        | Some head -> Some(head, bitArray.SliceBits(bits, bitArray.BitSize - bits).Value)
+        | None -> None
+
+    /// Reads an integer from all the bits of the bit array. As on Erlang, a
+    /// little endian integer whose size is not a multiple of 8 is stored as its
+    /// whole bytes, least significant first, followed by its remaining most
+    /// significant bits.
+    let private readInt (bits: BitArray) (signed: bool) (endianness: BitArrayEndianness) =
+        let size = bits.BitSize
+        let wholeBytes = size / 8L
+
+        // The index of the value's bit that is stored at the given position
+        let valueBit position =
+            if not endianness.IsLittleEndian then
+                size - 1L - position
+            elif position < wholeBytes * 8L then
+                position / 8L * 8L + 7L - position % 8L
             else
-                Some(System.Text.Encoding.UTF8.GetString(bytes))
-
-    let (|Empty|_|) (bitArray: BitArray) =
-        if bitArray.Length = 0 then Some() else None
-
-    // let (|Integer|_|) (buffer: byte[]) =
-
-    //     match IntFromSlice buffer (0, int64 buffer.Length) with
-    //     | Some i -> Some i
-    //     | None -> None
+                size - 1L - (position - wholeBytes * 8L)
 
-    //let chunk (bytes: byte[]) (sizes: int64 seq) =
+        let mutable unsigned = 0I
 
-    let (|Sections|_|) (sizes: int64 seq) (bitArray: BitArray) =
-        let mutable cursor = 0L
+        for position in 0L .. size - 1L do
+            if bits.Bit position then
+                unsigned <- unsigned ||| (1I <<< int (valueBit position))
 
-        let totalSize = sizes |> Seq.sum
-
-        if totalSize > bitArray.Length then
-            None
+        if signed && size > 0L && unsigned >= (1I <<< int (size - 1L)) then
+            unsigned - (1I <<< int size)
         else
-            Some [
-                for size in sizes do
-                    let slice = bitArray.SliceBuffer(cursor, size)
-
-                    match slice with
-                    | Ok slice ->
-                        cursor <- cursor + size
-                        yield slice
-                    | Error _ -> ()
+            unsigned
 
-                if cursor < bitArray.Length then
-                    yield bitArray.SliceAfter(cursor)
-
-            ]
+    let (|Empty|_|) (bitArray: BitArray) =
+        if bitArray.BitSize = 0L then Some() else None
 
+    /// Matches a bit array that is a whole number of bytes, as a trailing
+    /// `bytes` segment requires
+    let (|WholeBytes|_|) (bitArray: BitArray) =
+        if bitArray.IsByteAligned then Some bitArray else None
 
-    let (|Int64|_|) (bitArray: BitArray) = Segment.(|Int64|_|) bitArray.Buffer
+    let (|IntSegment|_|) (size: int64, signed: bool, endianness: BitArrayEndianness) (bitArray: BitArray) =
+        match splitAt size bitArray with
+        | Some(bytes, rest) ->
+            let value = readInt bytes signed endianness
 
-    let (|UInt64|_|) (bitArray: BitArray) = Segment.(|UInt64|_|) bitArray.Buffer
+            if value >= bigint Int64.MinValue && value <= bigint Int64.MaxValue then
+                Some(int64 value, rest)
+            else
+                None
+        | None -> None
+
+    let (|BigIntSegment|_|) (size: int64, signed: bool, endianness: BitArrayEndianness) (bitArray: BitArray) =
+        splitAt size bitArray
+        |> Option.map (fun (bits, rest) -> readInt bits signed endianness, rest)
+
+    let (|FloatSegment|_|) (size: int64, endianness: BitArrayEndianness) (bitArray: BitArray) =
+        match splitAt size bitArray with
+        | Some(bits, rest) ->
+            let bytes =
+                if endianness.IsLittleEndian = BitConverter.IsLittleEndian then
+                    bits.Buffer
+                else
+                    Array.rev bits.Buffer
 
-    let (|WithLength|_|) (length: int64) (bitArray: BitArray) =
-        if bitArray.LongLength = length then
-            Some(bitArray)
-        else
-            None
+            let value =
+                match size with
+                | 64L -> Some(BitConverter.ToDouble(bytes, 0))
+                | 32L -> Some(float (BitConverter.ToSingle(bytes, 0)))
+                | 16L -> Some(float (BitConverter.ToHalf(bytes, 0)))
+                | _ -> None
 
-    let (|Float64|_|) (bitArray: BitArray) = Segment.(|Float64|_|) bitArray.Buffer
+            // As on Erlang, NaN and infinity do not match float segments
+            match value with
+            | Some value when Double.IsFinite(value) -> Some(value, rest)
+            | _ -> None
+        | None -> None
 
-    let (|Byte|_|) (bitArray: BitArray) = Segment.(|Byte|_|) bitArray.Buffer
+    let (|BitsSegment|_|) (size: int64) (bitArray: BitArray) = splitAt size bitArray
 
-    let (|Bits|_|) (bitArray: BitArray) = Segment.(|Bits|_|) bitArray.Buffer
+    let (|BytesSegment|_|) (size: int64) (bitArray: BitArray) = splitAt (size * 8L) bitArray
 
-    let (|SizedBits|_|) (size: int64) (bitArray: BitArray) =
-        Segment.(|SizedBits|_|) size bitArray.Buffer
+    // Active pattern arguments can't contain arithmetic, so segments with a
+    // variable size and a unit other than 1 or 8 take the unit separately
 
+    let (|UnitsSegment|_|) (size: int64, unit: int64) (bitArray: BitArray) = splitAt (size * unit) bitArray
 
+    let (|IntUnitsSegment|_|) (size: int64, unit: int64, signed: bool, endianness: BitArrayEndianness) =
+        (|IntSegment|_|) (size * unit, signed, endianness)
 
-    // let (|Bytes|_|) (bitArray: BitArray) = Some(bitArray.Buffer)
+    let (|BigIntUnitsSegment|_|) (size: int64, unit: int64, signed: bool, endianness: BitArrayEndianness) =
+        (|BigIntSegment|_|) (size * unit, signed, endianness)
 
-    // let (|Utf8|_|) (bitArray: BitArray) =
-    //     bitArray.TryToUtf8String() |> Result.toOption
+    let (|FloatUnitsSegment|_|) (size: int64, unit: int64, endianness: BitArrayEndianness) =
+        (|FloatSegment|_|) (size * unit, endianness)
 
-    // let (|Utf16|_|) (bitArray: BitArray) =
-    //     try
-    //         System.Text.Encoding.Unicode.GetString(bitArray.Buffer) |> Some
-    //     with _ ->
-    //         None
+    let private encodedSegment (expected: byte[]) (bitArray: BitArray) =
+        match splitAt (int64 expected.Length * 8L) bitArray with
+        | Some(bits, rest) when bits.Buffer = expected -> Some rest
+        | _ -> None
 
-    // let (|Utf32|_|) (bitArray: BitArray) =
-    //     try
-    //         System.Text.Encoding.UTF32.GetString(bitArray.Buffer) |> Some
-    //     with _ ->
-    //         None
+    let (|Utf8Segment|_|) (expected: string) (bitArray: BitArray) =
+        encodedSegment (Text.Encoding.UTF8.GetBytes(expected)) bitArray
 
-    let tester () =
+    let (|Utf16Segment|_|) (expected: string, endianness: BitArrayEndianness) (bitArray: BitArray) =
+        encodedSegment (endianness.Utf16.GetBytes(expected)) bitArray
 
-        let bitArray =
-            BitArray.Create(BitArraySegment.FromInt64(1L), BitArraySegment.FromFloat(1.0))
+    let (|Utf32Segment|_|) (expected: string, endianness: BitArrayEndianness) (bitArray: BitArray) =
+        encodedSegment (endianness.Utf32.GetBytes(expected)) bitArray
 
-        let h = bitArray.IntFromSlice(0, sizeof<int64>)
-        let rest = bitArray.SliceAfter(sizeof<int64>)
+    let (|Utf8CodepointSegment|_|) (bitArray: BitArray) =
+        let status, rune, consumed =
+            Text.Rune.DecodeFromUtf8(ReadOnlySpan(bitArray.Buffer))
 
-        let f = bitArray.FloatFromSlice(sizeof<int64>, sizeof<float>)
+        match splitAt (int64 consumed * 8L) bitArray with
+        | Some(_, rest) when status = Buffers.OperationStatus.Done -> Some(UtfCodepoint rune, rest)
+        | _ -> None
 
-        printfn "%A" h
-        printfn "%A" rest
-        printfn "%A" f
-        ()
+    let (|Utf16CodepointSegment|_|) (endianness: BitArrayEndianness) (bitArray: BitArray) =
+        let bytes = bitArray.Buffer
 
-// let tester2 () =
-//     let bitArray =
-//         BitArray.Create(BitArraySegment.FromInt64(1L), BitArraySegment.FromFloat(1.0))
+        let codeUnit index =
+            let first, second = int bytes[index], int bytes[index + 1]
 
-//     match bitArray with
-//     | Sections [ 8; 8 ] (head, tail) -> printfn "%A" bitArray
-//     | _ -> printfn "No match"
+            if endianness.IsLittleEndian then
+                char ((second <<< 8) ||| first)
+            else
+                char ((first <<< 8) ||| second)
+
+        // A codepoint is one code unit, or two for a surrogate pair
+        let wholeBytes = int (bitArray.BitSize / 8L)
+        let codeUnits = [| for index in 0..2 .. min wholeBytes 4 - 2 -> codeUnit index |]
+        let status, rune, consumed = Text.Rune.DecodeFromUtf16(ReadOnlySpan(codeUnits))
+
+        match splitAt (int64 consumed * 16L) bitArray with
+        | Some(_, rest) when status = Buffers.OperationStatus.Done -> Some(UtfCodepoint rune, rest)
+        | _ -> None
+
+    let (|Utf32CodepointSegment|_|) (endianness: BitArrayEndianness) (bitArray: BitArray) =
+        match splitAt 32L bitArray with
+        | Some(bits, rest) ->
+            let value: uint32 = readInt bits false endianness |> uint32
+
+            match Text.Rune.TryCreate(value) with
+            | true, rune -> Some(UtfCodepoint rune, rest)
+            | false, _ -> None
+        | None -> None
@@ -0,0 +1,227 @@
+//! A model of an MSBuild project file, used to generate the `.fsproj` files
+//! for F# packages.
+
+use itertools::Itertools;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectFile {
+    pub sdk: String,
+    pub property_groups: Vec<PropertyGroup>,
+    pub item_groups: Vec<ItemGroup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PropertyGroup {
+    pub label: Option<String>,
+    pub properties: Vec<(String, String)>,
+}
+
+impl PropertyGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn labelled(label: &str) -> Self {
+        Self {
+            label: Some(label.into()),
+            properties: vec![],
+        }
+    }
+
+    pub fn property(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Sets the property, replacing the value of any existing property with
+    /// the same name.
+    pub fn set(&mut self, name: &str, value: impl Into<String>) {
+        let value = value.into();
+        match self.properties.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.properties.push((name.into(), value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ItemGroup {
+    pub label: Option<String>,
+    pub items: Vec<Item>,
+}
+
+impl ItemGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn labelled(label: &str) -> Self {
+        Self {
+            label: Some(label.into()),
+            items: vec![],
+        }
+    }
+
+    pub fn item(mut self, item: Item) -> Self {
+        self.items.push(item);
+        self
+    }
+}
+
+/// An item such as `<Compile Include="file.fs" />`. Attributes are written on
+/// the element itself, metadata as child elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    pub kind: String,
+    pub include: String,
+    pub attributes: Vec<(String, String)>,
+    pub metadata: Vec<(String, String)>,
+}
+
+impl Item {
+    pub fn new(kind: &str, include: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            include: include.into(),
+            attributes: vec![],
+            metadata: vec![],
+        }
+    }
+
+    pub fn attribute(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.set_attribute(name, value);
+        self
+    }
+
+    pub fn set_attribute(&mut self, name: &str, value: impl Into<String>) {
+        let value = value.into();
+        match self.attributes.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.attributes.push((name.into(), value)),
+        }
+    }
+
+    pub fn metadata(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.metadata.push((name.into(), value.into()));
+        self
+    }
+}
+
+impl ProjectFile {
+    pub fn new(sdk: &str) -> Self {
+        Self {
+            sdk: sdk.into(),
+            property_groups: vec![],
+            item_groups: vec![],
+        }
+    }
+
+    /// Sets a property in the first property group, replacing any existing
+    /// property with the same name in any group.
+    pub fn set_property(&mut self, name: &str, value: impl Into<String>) {
+        let value = value.into();
+        for group in self.property_groups.iter_mut() {
+            if let Some((_, existing)) = group.properties.iter_mut().find(|(n, _)| n == name) {
+                *existing = value;
+                return;
+            }
+        }
+        match self.property_groups.first_mut() {
+            Some(group) => group.set(name, value),
+            None => self
+                .property_groups
+                .push(PropertyGroup::new().property(name, value)),
+        }
+    }
+
+    /// Adds an item, merging its attributes into any existing item of the same
+    /// kind that includes the same path. New items are added to a group with
+    /// the given label.
+    pub fn merge_item(&mut self, label: &str, item: Item) {
+        let existing = self
+            .item_groups
+            .iter_mut()
+            .flat_map(|group| group.items.iter_mut())
+            .find(|existing| existing.kind == item.kind && existing.include == item.include);
+
+        if let Some(existing) = existing {
+            for (name, value) in item.attributes {
+                existing.set_attribute(&name, value);
+            }
+            existing.metadata.extend(item.metadata);
+            return;
+        }
+
+        match self
+            .item_groups
+            .iter_mut()
+            .find(|group| group.label.as_deref() == Some(label))
+        {
+            Some(group) => group.items.push(item),
+            None => self.item_groups.push(ItemGroup::labelled(label).item(item)),
+        }
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::new();
+        _ = writeln!(xml, "<Project Sdk=\"{}\">", escape(&self.sdk));
+
+        for group in self.property_groups.iter() {
+            if group.properties.is_empty() {
+                continue;
+            }
+            _ = writeln!(xml, "  <PropertyGroup{}>", label_attribute(&group.label));
+            for (name, value) in group.properties.iter() {
+                _ = writeln!(xml, "    <{name}>{}</{name}>", escape(value));
+            }
+            _ = writeln!(xml, "  </PropertyGroup>");
+        }
+
+        for group in self.item_groups.iter() {
+            if group.items.is_empty() {
+                continue;
+            }
+            _ = writeln!(xml, "  <ItemGroup{}>", label_attribute(&group.label));
+            for item in group.items.iter() {
+                let attributes = item
+                    .attributes
+                    .iter()
+                    .map(|(name, value)| format!(" {name}=\"{}\"", escape(value)))
+                    .join("");
+                let start = format!(
+                    "    <{} Include=\"{}\"{attributes}",
+                    item.kind,
+                    escape(&item.include)
+                );
+                if item.metadata.is_empty() {
+                    _ = writeln!(xml, "{start} />");
+                } else {
+                    _ = writeln!(xml, "{start}>");
+                    for (name, value) in item.metadata.iter() {
+                        _ = writeln!(xml, "      <{name}>{}</{name}>", escape(value));
+                    }
+                    _ = writeln!(xml, "    </{}>", item.kind);
+                }
+            }
+            _ = writeln!(xml, "  </ItemGroup>");
+        }
+
+        xml.push_str("</Project>\n");
+        xml
+    }
+}
+
+fn label_attribute(label: &Option<String>) -> String {
+    match label {
+        Some(label) => format!(" Label=\"{}\"", escape(label)),
+        None => String::new(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -3,7 +3,7 @@ use camino::Utf8PathBuf;
 use crate::{
     analyse::TargetSupport,
     build::{Origin, Target},
-    config::PackageConfig,
+    config::{FSharpConfig, PackageConfig},
     line_numbers::LineNumbers,
     type_::PRELUDE_MODULE_NAME,
     uid::UniqueIdGenerator,
@@ -16,12 +16,15 @@ mod bools;
 mod case;
 mod case_clause_guards;
 mod consts;
+mod csharp_interop;
 mod custom_types;
 mod docs;
 mod external_fn;
+mod external_types;
 mod functions;
 mod generics;
 mod imports;
+mod line_directives;
 mod lists;
 mod numbers;
 mod panic;
@@ -32,6 +35,7 @@ mod records;
 mod recursion;
 mod results;
 mod strings;
+mod test_frameworks;
 mod todo;
 mod tuples;
 mod type_alias;
@@ -42,6 +46,15 @@ pub static CURRENT_PACKAGE: &str = "thepackage";
 
 #[macro_export]
 macro_rules! assert_fsharp {
+    ($(($dep_package:expr, $dep_name:expr, $dep_src:expr)),+; $src:expr $(,)?) => {{
+        let output = $crate::fsharp::tests::compile_test_project(
+            $src,
+            vec![$(($dep_package, $dep_name, $dep_src)),+],
+        )
+        .expect("compilation failed");
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
+
     (($dep_package:expr, $dep_name:expr, $dep_src:expr), $src:expr $(,)?) => {{
         let output = $crate::fsharp::tests::compile_test_project(
             $src,
@@ -58,6 +71,42 @@ macro_rules! assert_fsharp {
     }};
 }
 
+#[macro_export]
+macro_rules! assert_fsharp_with_config {
+    ($config:expr, $src:expr $(,)?) => {{
+        let output = $crate::fsharp::tests::compile_test_project_with_config($src, vec![], $config)
+            .expect("compilation failed");
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
+}
+
+/// Compiles the source as a module in the `test` directory, with the given
+/// F# config
+#[macro_export]
+macro_rules! assert_fsharp_test_module {
+    ($config:expr, $src:expr $(,)?) => {{
+        let output = $crate::fsharp::tests::compile_test_module_with_config($src, $config)
+            .expect("compilation failed");
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
+}
+
+/// Compiles the source with `#line` directives, as is done when building a
+/// project
+#[macro_export]
+macro_rules! assert_fsharp_with_line_directives {
+    ($config:expr, $src:expr $(,)?) => {{
+        let output =
+            $crate::fsharp::tests::compile_test_project_with_line_directives($src, $config)
+                .expect("compilation failed");
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
+
+    ($src:expr $(,)?) => {{
+        $crate::assert_fsharp_with_line_directives!($crate::config::FSharpConfig::default(), $src)
+    }};
+}
+
 #[macro_export]
 macro_rules! assert_fsharp_with_multiple_imports {
     ($(($name:literal, $module_src:literal)),*; $src:literal) => {
@@ -89,6 +138,69 @@ pub fn compile_test_project(
     src: &str,
     deps: Vec<(&str, &str, &str)>,
 ) -> Result<String, crate::Error> {
+    compile_test_project_with_config(src, deps, FSharpConfig::default())
+}
+
+#[track_caller]
+pub fn compile_test_project_with_config(
+    src: &str,
+    deps: Vec<(&str, &str, &str)>,
+    fsharp_config: FSharpConfig,
+) -> Result<String, crate::Error> {
+    compile(src, deps, fsharp_config, Origin::Src, false)
+}
+
+#[track_caller]
+pub fn compile_test_project_with_line_directives(
+    src: &str,
+    fsharp_config: FSharpConfig,
+) -> Result<String, crate::Error> {
+    compile(src, vec![], fsharp_config, Origin::Src, true)
+}
+
+#[track_caller]
+pub fn compile_test_module_with_config(
+    src: &str,
+    fsharp_config: FSharpConfig,
+) -> Result<String, crate::Error> {
+    compile(src, vec![], fsharp_config, Origin::Test, false)
+}
+
+#[track_caller]
+fn compile(
+    src: &str,
+    deps: Vec<(&str, &str, &str)>,
+    fsharp_config: FSharpConfig,
+    origin: Origin,
+    line_directives: bool,
+) -> Result<String, crate::Error> {
+    let (config, module) = analyse(src, deps, fsharp_config, origin);
+    let mut generator =
+        crate::fsharp::Generator::new(&config.name, &module, &module.input_path, &config.fsharp);
+    generator.line_directives = line_directives;
+    generator.render()
+}
+
+/// The external F# files the generated code for the module needs to be
+/// compiled with
+#[track_caller]
+pub fn compile_external_files(src: &str) -> Vec<(Utf8PathBuf, Origin)> {
+    let (config, module) = analyse(src, vec![], FSharpConfig::default(), Origin::Src);
+    let mut generator =
+        crate::fsharp::Generator::new(&config.name, &module, &module.input_path, &config.fsharp);
+    let _ = generator.render().expect("compilation failed");
+    let mut files: Vec<_> = generator.external_files.into_iter().collect();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    files
+}
+
+#[track_caller]
+fn analyse(
+    src: &str,
+    deps: Vec<(&str, &str, &str)>,
+    fsharp_config: FSharpConfig,
+    origin: Origin,
+) -> (PackageConfig, crate::build::Module) {
     let mut modules = im::HashMap::new();
     let ids = UniqueIdGenerator::new();
     // DUPE: preludeinsertion
@@ -134,14 +246,14 @@ pub fn compile_test_project(
         .expect("syntax error");
     let mut config = PackageConfig::default();
     config.name = "thepackage".into();
-    config.fsharp.type_mappings = std::collections::HashMap::from_iter(vec![]);
+    config.fsharp = fsharp_config;
     let mut ast = parsed.module;
     ast.name = "my/mod".into();
     let line_numbers = LineNumbers::new(src);
     let module = crate::analyse::ModuleAnalyzerConstructor::<()> {
         target: Target::FSharp,
         ids: &ids,
-        origin: Origin::Src,
+        origin,
         importable_modules: &modules,
         warnings: &TypeWarningEmitter::null(),
         direct_dependencies: &direct_dependencies,
@@ -151,9 +263,18 @@ pub fn compile_test_project(
     .infer_module(ast, line_numbers, path.clone())
     .expect("should successfully infer root FSharp");
 
-    let mut generator =
-        crate::fsharp::Generator::new(&config.name, &module, &path, &config.fsharp.type_mappings);
-    generator.render()
+    let module = crate::build::Module {
+        name: "my/mod".into(),
+        code: src.into(),
+        mtime: std::time::SystemTime::UNIX_EPOCH,
+        input_path: path.clone(),
+        origin,
+        ast: module,
+        extra: Default::default(),
+        dependencies: vec![],
+    };
+
+    (config, module)
 }
 
 pub fn expect_fsharp_error(src: &str, deps: Vec<(&str, &str, &str)>) -> String {
@@ -187,16 +187,123 @@ fn go(x) {
     );
 }
 
-// #[test]
-// fn match_utf8() {
-//     assert_fsharp!(
-//         r#"
-// fn go(x) {
-//   let assert <<"Gleam 👍":utf8>> = x
-// }
-// "#,
-//     );
-// }
+#[test]
+fn match_utf8() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  let assert <<"Gleam 👍":utf8>> = x
+  Nil
+}
+"#,
+    );
+}
+
+#[test]
+fn utf16() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  <<x:utf16, "Gleam":utf16>>
+}
+"#,
+    );
+}
+
+#[test]
+fn utf16_little_endian() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  <<x:utf16-little, "Gleam":utf16-little>>
+}
+"#,
+    );
+}
+
+#[test]
+fn utf32() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  <<x:utf32, "Gleam":utf32>>
+}
+"#,
+    );
+}
+
+#[test]
+fn utf32_little_endian() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  <<x:utf32-little, "Gleam":utf32-little>>
+}
+"#,
+    );
+}
+
+#[test]
+fn match_utf16() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  let assert <<"Gleam 👍":utf16>> = x
+  Nil
+}
+"#,
+    );
+}
+
+#[test]
+fn match_utf16_little_endian() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  let assert <<"Gleam":utf16-little, rest:bytes>> = x
+  rest
+}
+"#,
+    );
+}
+
+#[test]
+fn match_utf32() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  let assert <<"Gleam":utf32, rest:bytes>> = x
+  rest
+}
+"#,
+    );
+}
+
+#[test]
+fn match_utf32_native_endian() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  let assert <<"Gleam":utf32-native, rest:bytes>> = x
+  rest
+}
+"#,
+    );
+}
+
+#[test]
+fn discard_utf16_pattern() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  case x {
+    <<_:utf16-little, rest:bits>> -> rest
+    _ -> x
+  }
+}
+"#,
+    );
+}
 
 #[test]
 fn utf8_codepoint() {
@@ -209,6 +316,64 @@ fn go(x) {
     );
 }
 
+#[test]
+fn utf16_codepoint() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  <<x:utf16_codepoint, "Gleam":utf16>>
+}
+"#,
+    );
+}
+
+#[test]
+fn utf32_codepoint() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  <<x:utf32_codepoint, "Gleam":utf32>>
+}
+"#,
+    );
+}
+
+#[test]
+fn match_utf8_codepoint() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  let assert <<a:utf8_codepoint, rest:bytes>> = x
+  #(a, rest)
+}
+"#,
+    );
+}
+
+#[test]
+fn match_utf16_codepoint() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  let assert <<a:utf16_codepoint, b:utf16_codepoint>> = x
+  #(a, b)
+}
+"#,
+    );
+}
+
+#[test]
+fn match_utf32_codepoint() {
+    assert_fsharp!(
+        r#"
+fn go(x) {
+  let assert <<a:utf32_codepoint, b:utf32_codepoint>> = x
+  #(a, b)
+}
+"#,
+    );
+}
+
 #[test]
 fn bit_string() {
     assert_fsharp!(
@@ -800,3 +965,52 @@ pub fn go() {
 }"#
     );
 }
+
+#[test]
+fn unaligned_segments() {
+    assert_fsharp!(
+        r#"
+pub fn go(x, y, z) {
+  <<x:3, y:5-little, 1:size(2)-unit(3), z:bits-size(x)>>
+}"#
+    );
+}
+
+#[test]
+fn match_unaligned_segments() {
+    assert_fsharp!(
+        r#"
+pub fn go(x) {
+  case x {
+    <<a:3, b:5-little-signed, rest:bits>> -> #(a, b, rest)
+    _ -> #(0, 0, x)
+  }
+}"#
+    );
+}
+
+#[test]
+fn match_variable_size_with_unit() {
+    assert_fsharp!(
+        r#"
+pub fn go(x) {
+  case x {
+    <<size:4, a:size(size)-unit(3), b:bits-size(size)-unit(5), _:bits>> -> #(a, b)
+    _ -> #(0, x)
+  }
+}"#
+    );
+}
+
+#[test]
+fn match_rest_bytes() {
+    assert_fsharp!(
+        r#"
+pub fn go(x) {
+  case x {
+    <<_:4, rest:bytes>> -> rest
+    _ -> x
+  }
+}"#
+    );
+}
@@ -0,0 +1,104 @@
+use crate::config::FSharpConfig;
+use crate::{assert_fsharp_with_config, assert_fsharp_with_line_directives};
+
+fn config() -> FSharpConfig {
+    FSharpConfig {
+        csharp_interop: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn public_functions() {
+    assert_fsharp_with_config!(
+        config(),
+        r#"
+pub fn add_numbers(a: Int, b: Int) -> Int {
+  a + b
+}
+
+pub fn greet() -> String {
+  "Hello"
+}
+
+fn private_helper(x) {
+  x
+}
+"#
+    );
+}
+
+#[test]
+fn lists_are_read_only_lists() {
+    assert_fsharp_with_config!(
+        config(),
+        r#"
+pub fn reverse_all(items: List(a), _extra: Int) -> List(a) {
+  case items {
+    [] -> []
+    [x, ..rest] -> [x, ..reverse_all(rest, 0)]
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn no_public_functions() {
+    assert_fsharp_with_config!(
+        config(),
+        r#"
+fn private_helper(x) {
+  x
+}
+"#
+    );
+}
+
+#[test]
+fn public_type_fields_have_compiled_names() {
+    assert_fsharp_with_config!(
+        config(),
+        r#"
+pub type Person {
+  Person(first_name: String, age: Int)
+}
+
+pub type Shape {
+  Circle(line_width: Int, radius: Float)
+  Square(line_width: Int, side: Float)
+}
+
+type Private {
+  Private(some_field: Int)
+}
+"#
+    );
+}
+
+#[test]
+fn nested_lists_are_not_converted() {
+    assert_fsharp_with_config!(
+        config(),
+        r#"
+pub fn pairs(items: #(List(Int), Int), f: fn(List(Int)) -> Int) -> List(List(Int)) {
+  [items.0, [f(items.0)]]
+}
+"#
+    );
+}
+
+#[test]
+fn interop_methods_have_line_directives() {
+    assert_fsharp_with_line_directives!(
+        config(),
+        r#"pub fn add_numbers(a: Int, b: Int) -> Int {
+  a + b
+}
+
+pub fn greet() -> String {
+  "Hello"
+}
+"#
+    );
+}
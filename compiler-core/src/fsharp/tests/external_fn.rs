@@ -1,4 +1,8 @@
+use camino::Utf8PathBuf;
+
 use crate::assert_fsharp;
+use crate::build::Origin;
+use crate::fsharp::tests::compile_external_files;
 
 #[test]
 fn external_fn_from_std_lib() {
@@ -14,6 +18,102 @@ pub fn foo() -> Int {
     );
 }
 
+#[test]
+fn external_static_method() {
+    assert_fsharp!(
+        r#"
+@external(fsharp, "System.Math", "Max()")
+pub fn max(a: Int, b: Int) -> Int
+
+@external(fsharp, "System.String", "Concat()")
+pub fn concat(a: String, b: String) -> String
+"#
+    );
+}
+
+#[test]
+fn external_constructor() {
+    assert_fsharp!(
+        r#"
+@external(fsharp, "System", "Uri")
+pub type Uri
+
+@external(fsharp, "System.Uri", "new")
+pub fn parse(string: String) -> Uri
+"#
+    );
+}
+
+#[test]
+fn external_instance_method() {
+    assert_fsharp!(
+        r#"
+@external(fsharp, "System.String", ".Replace()")
+pub fn replace(string: String, pattern: String, with: String) -> String
+
+@external(fsharp, "System.String", ".ToUpperInvariant()")
+pub fn uppercase(string: String) -> String
+"#
+    );
+}
+
+#[test]
+fn external_property_getter() {
+    assert_fsharp!(
+        r#"
+@external(fsharp, "System", "Uri")
+pub type Uri
+
+@external(fsharp, "System.Uri", ".Host")
+pub fn host(uri: Uri) -> String
+"#
+    );
+}
+
+#[test]
+fn external_curried_function() {
+    assert_fsharp!(
+        r#"
+@external(fsharp, "List", "append")
+pub fn append(a: List(x), b: List(x)) -> List(x)
+"#
+    );
+}
+
+#[test]
+fn external_quoted_function_name() {
+    assert_fsharp!(
+        r#"
+@external(fsharp, "MyModule", "``add numbers``")
+pub fn add(a: Int, b: Int) -> Int
+"#
+    );
+}
+
+const MEMBERS_FROM_FILE: &str = r#"
+@external(fsharp, "./ffi.fs", ".Replace()")
+pub fn replace(string: String, pattern: String, with: String) -> String
+
+@external(fsharp, "./ffi.fs", ".Length")
+pub fn length(string: String) -> Int
+"#;
+
+#[test]
+fn external_members_from_file() {
+    assert_fsharp!(MEMBERS_FROM_FILE);
+}
+
+#[test]
+fn external_members_from_file_include_the_file() {
+    assert_eq!(
+        compile_external_files(MEMBERS_FROM_FILE),
+        vec![(
+            Utf8PathBuf::from("/root/project/test/my/./ffi.fs"),
+            Origin::Src
+        )]
+    );
+}
+
 // #[test]
 // fn external_fn_from_file() {
 //     assert_fsharp!(
@@ -0,0 +1,47 @@
+use crate::assert_fsharp;
+
+#[test]
+fn external_type() {
+    assert_fsharp!(
+        r#"
+@external(fsharp, "System.Text", "StringBuilder")
+pub type StringBuilder
+"#
+    );
+}
+
+#[test]
+fn generic_external_type() {
+    assert_fsharp!(
+        r#"
+@external(fsharp, "System.Collections.Generic", "Dictionary`2")
+pub type Dictionary(key, value)
+
+@external(fsharp, "System.Collections.Generic", "Stack")
+pub type Stack(a)
+"#
+    );
+}
+
+#[test]
+fn external_type_used_in_function() {
+    assert_fsharp!(
+        r#"
+/// A last-in-first-out collection
+@external(fsharp, "System.Collections.Generic", "Stack`1")
+pub type Stack(a)
+
+@external(fsharp, "System.Collections.Generic.Stack", "Peek")
+pub fn peek(stack: Stack(a)) -> a
+"#
+    );
+}
+
+#[test]
+fn external_type_without_attribute_is_not_rendered() {
+    assert_fsharp!(
+        r#"
+pub type Phantom
+"#
+    );
+}
@@ -1,4 +1,12 @@
-use crate::assert_fsharp;
+use crate::config::{FSharpConfig, FSharpOutputType};
+use crate::{assert_fsharp, assert_fsharp_with_config};
+
+fn exe_config() -> FSharpConfig {
+    FSharpConfig {
+        output_type: FSharpOutputType::Exe,
+        ..Default::default()
+    }
+}
 
 #[test]
 fn function_as_value() {
@@ -30,7 +38,8 @@ pub fn foo() {
 
 #[test]
 fn entry_point_main_function_with_no_args() {
-    assert_fsharp!(
+    assert_fsharp_with_config!(
+        exe_config(),
         "
 pub fn main() {
     0
@@ -40,7 +49,8 @@ pub fn main() {
 }
 #[test]
 fn entry_point_main_function_with_args() {
-    assert_fsharp!(
+    assert_fsharp_with_config!(
+        exe_config(),
         "
 pub fn main(args) {
     0
@@ -133,3 +143,32 @@ fn go(){
 // "#
 //     );
 // }
+
+#[test]
+fn constructors_as_values() {
+    assert_fsharp!(
+        r#"
+pub type Person {
+  Person(name: String, age: Int)
+}
+
+pub type Shape {
+  Circle(Float)
+  Rectangle(Float, Float)
+}
+
+fn apply2(constructor: fn(a, b) -> c, a: a, b: b) -> c {
+  constructor(a, b)
+}
+
+pub fn go() {
+  #(
+    apply2(Person, "Louis", 30),
+    apply2(Rectangle, 1.0, 2.0),
+    Circle,
+    Rectangle(1.0, 2.0),
+  )
+}
+"#
+    );
+}
@@ -46,3 +46,157 @@ fn result() {
           }"#,
     );
 }
+
+#[test]
+fn generic_signatures_are_explicit() {
+    assert_fsharp!(
+        r#"pub fn map_error(result: Result(a, e), f: fn(e) -> f) -> Result(a, f) {
+  case result {
+    Ok(value) -> Ok(value)
+    Error(error) -> Error(f(error))
+  }
+}
+
+pub fn go() {
+  let x = []
+  map_error(Ok(x), fn(e) { e + 1 })
+}
+"#,
+    );
+}
+
+#[test]
+fn generic_equality() {
+    assert_fsharp!(
+        r#"pub fn same(a: a, b: a) -> Bool {
+  a == b
+}
+
+pub fn different(a: List(a), b: List(a)) -> Bool {
+  case a {
+    _ if a != b -> True
+    _ -> a != b
+  }
+}
+"#,
+    );
+}
+
+#[test]
+fn dict_and_set_keys_are_comparison_constrained() {
+    assert_fsharp!(
+        (
+            "gleam_stdlib",
+            "gleam/dict",
+            r#"pub type Dict(key, value)
+@external(fsharp, "gleam.Dict", "empty")
+pub fn new() -> Dict(key, value)"#
+        ),
+        (
+            "gleam_stdlib",
+            "gleam/set",
+            r#"pub type Set(member)
+@external(fsharp, "gleam.Set", "empty")
+pub fn new() -> Set(member)"#
+        );
+        r#"import gleam/dict.{type Dict}
+import gleam/set.{type Set}
+
+pub fn lookup(dict: Dict(#(k, Int), v), key: k, default: v) -> v {
+  default
+}
+
+pub fn members(sets: List(Set(a)), fallback: b) -> Dict(b, Set(a)) {
+  dict.new()
+}
+"#
+    );
+}
+
+const DICT_MODULE: &str = r#"pub type Dict(key, value)
+@external(fsharp, "gleam.Dict", "empty")
+pub fn new() -> Dict(key, value)
+@external(fsharp, "gleam.Dict", "insert")
+pub fn insert(dict: Dict(key, value), key: key, value: value) -> Dict(key, value)"#;
+
+#[test]
+fn dict_keys_in_function_bodies_are_comparison_constrained() {
+    assert_fsharp!(
+        ("gleam_stdlib", "gleam/dict", DICT_MODULE),
+        r#"import gleam/dict
+
+pub fn count(items: List(a)) -> Int {
+  let counts = dict.new()
+  let _ = dict.insert(counts, items, 0)
+  0
+}
+"#
+    );
+}
+
+#[test]
+fn types_wrapping_dicts_are_comparison_constrained() {
+    assert_fsharp!(
+        ("gleam_stdlib", "gleam/dict", DICT_MODULE),
+        r#"import gleam/dict.{type Dict}
+
+pub type Cache(k, v) {
+  Cache(entries: Dict(k, v))
+}
+
+pub type Caches(k) {
+  Caches(caches: List(Cache(k, Int)))
+}
+
+pub fn empty() -> Caches(k) {
+  Caches([])
+}
+
+pub fn first(caches: Caches(k), default: Cache(k, Int)) -> Cache(k, Int) {
+  case caches.caches {
+    [cache, ..] -> cache
+    [] -> default
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn calls_to_constrained_functions_are_comparison_constrained() {
+    assert_fsharp!(
+        ("gleam_stdlib", "gleam/dict", DICT_MODULE),
+        r#"import gleam/dict
+
+pub fn go(item: a, other: b) -> b {
+  let _ = key(#(item, 1))
+  other
+}
+
+fn key(value: k) -> Int {
+  let _ = dict.insert(dict.new(), value, Nil)
+  case 0 {
+    0 -> 0
+    _ -> key(value)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn calls_to_generic_functions_from_other_modules_infer_type_parameters() {
+    assert_fsharp!(
+        ("other_package", "other", "pub fn identity(x) { x }"),
+        r#"import other
+
+pub fn wrap(x: a, y: b) -> #(a, b) {
+  #(other.identity(x), y)
+}
+
+pub fn go(y: b) -> #(Int, b) {
+  #(other.identity(1), y)
+}
+"#
+    );
+}
@@ -203,3 +203,17 @@ pub const variable = rocket_ship.class
 "#,
     );
 }
+
+#[test]
+fn imported_constructor_shadowed_by_variable() {
+    assert_fsharp!(
+        (
+            CURRENT_PACKAGE,
+            "rocket_ship",
+            r#"pub type Rocket { Rocket(fuel: Int) Grounded }"#
+        ),
+        r#"import rocket_ship.{Rocket}
+pub fn go(rocket_ship: Int) { Rocket(rocket_ship) }
+"#
+    );
+}
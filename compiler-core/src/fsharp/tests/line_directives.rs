@@ -0,0 +1,38 @@
+use crate::assert_fsharp_with_line_directives;
+
+#[test]
+fn definitions_and_statements() {
+    assert_fsharp_with_line_directives!(
+        r#"
+pub type Wibble {
+  Wibble(Int)
+}
+
+pub const wobble = 1
+
+pub fn main() {
+  let x = wobble
+  let y = {
+    let z = x + 1
+    z * 2
+  }
+  Wibble(y)
+}
+"#
+    );
+}
+
+#[test]
+fn anonymous_function() {
+    assert_fsharp_with_line_directives!(
+        r#"
+pub fn main() {
+  let f = fn(x) {
+    let y = x + 1
+    y
+  }
+  f(1)
+}
+"#
+    );
+}
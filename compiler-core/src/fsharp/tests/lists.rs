@@ -25,6 +25,23 @@ fn go(x) {
     );
 }
 
+#[test]
+fn list_literal_spread_operands() {
+    assert_fsharp!(
+        r#"
+fn go(x, xs, bs) {
+    [x + 1, id(x), ..xs]
+    [x == 1, ..bs]
+    [x, ..id(xs)]
+}
+
+fn id(x) {
+    x
+}
+"#,
+    );
+}
+
 #[test]
 fn long_list_literals() {
     assert_fsharp!(
@@ -1,4 +1,5 @@
-use crate::assert_fsharp;
+use crate::config::{FSharpConfig, FSharpIntRepresentation};
+use crate::{assert_fsharp, assert_fsharp_with_config};
 
 #[test]
 fn numbers_with_underscores() {
@@ -112,3 +113,194 @@ pub fn foo() {
 "#
     );
 }
+
+#[test]
+fn int_divide_by_zero() {
+    assert_fsharp!(
+        r#"
+pub fn main() {
+  let a = 10
+  let b = 0
+  a / b
+}
+"#
+    );
+}
+
+#[test]
+fn int_remainder() {
+    assert_fsharp!(
+        r#"
+pub fn main(x) {
+  x % 0
+  { x + 1 } % -3
+}
+"#
+    );
+}
+
+#[test]
+fn float_divide_by_zero() {
+    assert_fsharp!(
+        r#"
+pub fn main() {
+  let a = 5.0
+  a /. 0.0
+}
+"#
+    );
+}
+
+#[test]
+fn division_of_nested_expressions() {
+    assert_fsharp!(
+        r#"
+fn id(x) {
+  x
+}
+
+pub fn main(x) {
+  id(x) / { 2 * x } / 3
+}
+"#
+    );
+}
+
+#[test]
+fn division_in_guards() {
+    assert_fsharp!(
+        r#"
+pub fn main(x, y) {
+  case x {
+    _ if x / y > 1 -> 1
+    _ if x % 2 == 0 -> 2
+    _ if 1.0 /. 0.0 == 0.0 -> 3
+    _ -> 4
+  }
+}
+"#
+    );
+}
+
+fn bigint_config() -> FSharpConfig {
+    FSharpConfig {
+        int_representation: FSharpIntRepresentation::BigInt,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn bigint_literals() {
+    assert_fsharp_with_config!(
+        bigint_config(),
+        r#"
+pub fn main() {
+  let a = 100_000
+  let b = 0xffe0bb
+  let c = -8
+  let d = 123_456_789_123_456_789_123_456_789
+  #(a, b, c, d)
+}
+"#
+    );
+}
+
+#[test]
+fn bigint_arithmetic() {
+    assert_fsharp_with_config!(
+        bigint_config(),
+        r#"
+pub fn main(x: Int, y: Int) -> Bool {
+  let z = x * y + x / y - x % y
+  z > 10 && z <= y
+}
+"#
+    );
+}
+
+#[test]
+fn bigint_patterns() {
+    assert_fsharp_with_config!(
+        bigint_config(),
+        r#"
+pub fn main(x, y) {
+  case x, y {
+    0, _ -> "zero"
+    1, _ | 2, _ -> "small"
+    n, #(3, _) if n > 100 -> "big"
+    _, _ -> "other"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn bigint_constants() {
+    assert_fsharp_with_config!(
+        bigint_config(),
+        r#"
+const a = 1
+pub const b = [1, 2, 3]
+const c = 1.5
+
+pub fn main() {
+  #(a, b, c)
+}
+"#
+    );
+}
+
+#[test]
+fn bigint_bit_array_segments() {
+    assert_fsharp_with_config!(
+        bigint_config(),
+        r#"
+pub fn main(x) {
+  <<x:size(16), 1>>
+}
+"#
+    );
+}
+
+#[test]
+fn bigint_bit_array_pattern_sizes() {
+    assert_fsharp_with_config!(
+        bigint_config(),
+        r#"
+pub fn main(x) {
+  case x {
+    <<n, x:size(n), rest:bits-size(n)-unit(4)>> -> #(x, rest)
+    _ -> #(0, <<>>)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn bigint_bit_array_segments_out_of_int64_range() {
+    assert_fsharp_with_config!(
+        bigint_config(),
+        r#"
+pub fn main(size) {
+  <<123_456_789_123_456_789_123_456_789:size(size)>>
+}
+"#
+    );
+}
+
+#[test]
+fn negated_call() {
+    assert_fsharp!(
+        r#"
+pub fn go() {
+  -identity(1)
+}
+
+fn identity(x) {
+  x
+}
+"#
+    );
+}
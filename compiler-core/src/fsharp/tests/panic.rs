@@ -61,3 +61,19 @@ fn piped_chain() {
     "#
     );
 }
+
+#[test]
+fn panic_in_anonymous_function_uses_enclosing_function_name() {
+    assert_fsharp!(
+        r#"
+pub fn foo() {
+  fn(x) {
+    case x {
+      1 -> panic as "one"
+      _ -> x
+    }
+  }
+}
+"#
+    );
+}
@@ -336,3 +336,18 @@ fn do_unfold(
 // }"
 //     );
 // }
+
+#[test]
+fn record_update_of_union_type() {
+    assert_fsharp!(
+        r#"
+pub type Person {
+  User(name: String, age: Int, admin: Bool)
+}
+
+pub fn birthday(person: Person) -> Person {
+  User(..person, age: person.age + 1)
+}
+"#
+    );
+}
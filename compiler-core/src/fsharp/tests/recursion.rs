@@ -59,3 +59,108 @@ pub fn map(map) {
 "#
     );
 }
+
+// Without the loop this overflows the stack on .NET. This only checks the
+// generated loop: the 10 million recursion regression test is in the
+// `tail_call_optimisation_tests` of test/language, run by `make fsharp`.
+#[test]
+fn tco_self_recursion_is_a_loop() {
+    assert_fsharp!(
+        r#"
+pub fn main() {
+  count_down(from: 10_000_000)
+}
+
+fn count_down(from i) {
+  case i {
+    0 -> Nil
+    _ -> count_down(i - 1)
+  }
+}
+"#
+    );
+}
+
+// https://github.com/gleam-lang/gleam/issues/1214
+// https://github.com/gleam-lang/gleam/issues/1380
+#[test]
+fn tco_arguments_reassigned() {
+    assert_fsharp!(
+        r#"
+pub fn foo(x, y) {
+  case x {
+    0 -> y
+    _ -> foo(x - 1, [x, ..y])
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn tco_swapped_arguments() {
+    assert_fsharp!(
+        r#"
+pub fn foo(x, y, n) {
+  case n {
+    0 -> #(x, y)
+    _ -> foo(y, x, n - 1)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn tco_discarded_argument() {
+    assert_fsharp!(
+        r#"
+pub fn foo(x, _y) {
+  case x {
+    0 -> Nil
+    _ -> foo(x - 1, x)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn tco_pipeline() {
+    assert_fsharp!(
+        r#"
+pub fn foo(x) {
+  case x {
+    0 -> Nil
+    _ -> x - 1 |> foo
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn not_tco_in_anonymous_function() {
+    assert_fsharp!(
+        r#"
+pub fn foo(x) {
+  let f = fn() { foo(x - 1) }
+  f()
+}
+"#
+    );
+}
+
+#[test]
+fn not_tco_in_argument() {
+    assert_fsharp!(
+        r#"
+pub fn foo(x) {
+  case x {
+    0 -> 0
+    _ -> 1 + foo(x - 1)
+  }
+}
+"#
+    );
+}
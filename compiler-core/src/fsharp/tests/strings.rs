@@ -517,3 +517,14 @@ pub fn foo() {
 "#
     );
 }
+
+#[test]
+fn unicode_escape_sequences_are_padded() {
+    assert_fsharp!(
+        r#"
+pub fn go() -> String {
+  "caf\u{e9} \u{1F600}"
+}
+"#,
+    );
+}
@@ -0,0 +1,65 @@
+use crate::assert_fsharp_test_module;
+use crate::config::{FSharpConfig, FSharpTestFramework};
+
+fn config(test_framework: FSharpTestFramework) -> FSharpConfig {
+    FSharpConfig {
+        test_framework,
+        ..Default::default()
+    }
+}
+
+const TEST_MODULE: &str = r#"
+pub fn addition_test() {
+  Nil
+}
+
+pub fn helper(x) {
+  x
+}
+
+fn private_test() {
+  Nil
+}
+"#;
+
+#[test]
+fn xunit_test_functions() {
+    assert_fsharp_test_module!(config(FSharpTestFramework::default()), TEST_MODULE);
+}
+
+#[test]
+fn nunit_test_functions() {
+    assert_fsharp_test_module!(
+        config(FSharpTestFramework::NUnit {
+            version: "4.2.2".into(),
+            adapter_version: "4.6.0".into(),
+        }),
+        TEST_MODULE
+    );
+}
+
+#[test]
+fn mstest_test_functions() {
+    assert_fsharp_test_module!(
+        config(FSharpTestFramework::MsTest {
+            version: "3.6.1".into(),
+            adapter_version: "3.6.1".into(),
+        }),
+        TEST_MODULE
+    );
+}
+
+#[test]
+fn mstest_module_without_tests() {
+    assert_fsharp_test_module!(
+        config(FSharpTestFramework::MsTest {
+            version: "3.6.1".into(),
+            adapter_version: "3.6.1".into(),
+        }),
+        r#"
+pub fn helper(x) {
+  x
+}
+"#
+    );
+}
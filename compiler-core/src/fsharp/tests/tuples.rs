@@ -156,3 +156,18 @@ fn go(x) {
 "#
     )
 }
+
+#[test]
+fn single_value_tuple_of_call() {
+    assert_fsharp!(
+        r#"
+fn go() {
+  #(identity(1))
+}
+
+fn identity(x) {
+  x
+}
+"#,
+    );
+}
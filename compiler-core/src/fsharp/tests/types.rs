@@ -1,4 +1,5 @@
 use crate::assert_fsharp;
+use crate::fsharp::tests::CURRENT_PACKAGE;
 
 #[test]
 fn type_inferrence_produces_correct_type_params() {
@@ -96,3 +97,54 @@ pub fn decode2(
 "#,
     );
 }
+
+#[test]
+fn same_named_types_from_different_modules() {
+    assert_fsharp!(
+        (CURRENT_PACKAGE, "wibble", r#"pub type Error { Wibble }"#),
+        (CURRENT_PACKAGE, "wobble", r#"pub type Error { Wobble }"#);
+        r#"
+import wibble
+import wobble
+
+pub fn go(a: wibble.Error, b: wobble.Error) -> #(wibble.Error, wobble.Error) {
+  #(a, b)
+}
+"#,
+    );
+}
+
+#[test]
+fn same_named_types_from_different_packages() {
+    assert_fsharp!(
+        ("wibble", "wibble/error", r#"pub type Error(a) { Wibble(a) }"#),
+        ("wobble", "wobble/error", r#"pub type Error { Wobble }"#);
+        r#"
+import wibble/error as wibble_error
+import wobble/error.{type Error}
+
+pub fn go(a: wibble_error.Error(Error)) -> List(wibble_error.Error(Error)) {
+  [a]
+}
+"#,
+    );
+}
+
+#[test]
+fn local_type_with_same_name_as_imported_type() {
+    assert_fsharp!(
+        (CURRENT_PACKAGE, "wibble", r#"pub type Error { Wibble }"#),
+        r#"
+import wibble
+
+pub type Error {
+  Local(wibble.Error)
+}
+
+pub fn go(error: Error) -> wibble.Error {
+  let Local(inner) = error
+  inner
+}
+"#,
+    );
+}
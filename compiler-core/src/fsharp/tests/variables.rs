@@ -128,3 +128,14 @@ const a = 3
 "#
     );
 }
+
+#[test]
+fn returned_assignment_with_discarded_values() {
+    assert_fsharp!(
+        r#"
+pub fn go(pair: #(Int, Int)) -> #(Int, Int) {
+  let #(first, _) = pair
+}
+"#
+    );
+}
@@ -24,8 +24,12 @@ use camino::{Utf8Path, Utf8PathBuf};
 #[derive(Clone, Default, Debug, PartialEq, Eq)]
 pub struct InMemoryFileSystem {
     files: Rc<RefCell<HashMap<Utf8PathBuf, InMemoryFile>>>,
+    commands: Rc<RefCell<Vec<ExecutedCommand>>>,
 }
 
+/// The program and arguments of a command run by an `InMemoryFileSystem`
+pub type ExecutedCommand = (String, Vec<String>);
+
 impl InMemoryFileSystem {
     pub fn new() -> Self {
         Self::default()
@@ -52,6 +56,11 @@ impl InMemoryFileSystem {
         self.files.borrow().keys().cloned().collect()
     }
 
+    /// The programs and arguments of the commands that have been executed.
+    pub fn executed_commands(&self) -> Vec<ExecutedCommand> {
+        self.commands.borrow().clone()
+    }
+
     #[cfg(test)]
     /// Set the modification time of a file.
     ///
@@ -298,12 +307,15 @@ impl io::Write for InMemoryFile {
 impl CommandExecutor for InMemoryFileSystem {
     fn exec(
         &self,
-        _program: &str,
-        _args: &[String],
+        program: &str,
+        args: &[String],
         _env: &[(&str, String)],
         _cwd: Option<&Utf8Path>,
         _stdio: Stdio,
     ) -> Result<i32, Error> {
+        self.commands
+            .borrow_mut()
+            .push((program.to_string(), args.to_vec()));
         Ok(0) // Always succeed.
     }
 }
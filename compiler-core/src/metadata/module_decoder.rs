@@ -104,6 +104,7 @@ impl ModuleDecoder {
             type_,
             deprecation,
             documentation: self.optional_string(reader.get_documentation()?),
+            external_fsharp: self.optional_external(reader.get_external_fsharp()?)?,
         })
     }
 
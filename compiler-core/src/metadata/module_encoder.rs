@@ -206,6 +206,10 @@ impl<'a> ModuleEncoder<'a> {
                 .map(EcoString::as_str)
                 .unwrap_or_default(),
         );
+        self.build_external(
+            builder.reborrow().init_external_fsharp(),
+            &constructor.external_fsharp,
+        );
     }
 
     fn build_type_value_constructor(
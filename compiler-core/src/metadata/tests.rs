@@ -145,6 +145,7 @@ fn module_with_private_type() {
                 parameters: vec![],
                 deprecation: Deprecation::NotDeprecated,
                 documentation: None,
+                external_fsharp: None,
             },
         )]
         .into(),
@@ -176,6 +177,39 @@ fn module_with_app_type() {
                 parameters: vec![],
                 deprecation: Deprecation::NotDeprecated,
                 documentation: None,
+                external_fsharp: None,
+            },
+        )]
+        .into(),
+        types_value_constructors: HashMap::new(),
+        values: HashMap::new(),
+        accessors: HashMap::new(),
+        line_numbers: LineNumbers::new(""),
+        src_path: "some_path".into(),
+        minimum_required_version: Version::new(0, 1, 0),
+    };
+    assert_eq!(roundtrip(&module), module);
+}
+
+#[test]
+fn module_with_fsharp_external_type() {
+    let module = ModuleInterface {
+        warnings: vec![],
+        is_internal: false,
+        package: "some_package".into(),
+        origin: Origin::Src,
+        name: "a/b".into(),
+        types: [(
+            "Stack".into(),
+            TypeConstructor {
+                type_: type_::generic_var(0),
+                publicity: Publicity::Public,
+                origin: Default::default(),
+                module: "a/b".into(),
+                parameters: vec![type_::generic_var(0)],
+                deprecation: Deprecation::NotDeprecated,
+                documentation: None,
+                external_fsharp: Some(("System.Collections.Generic".into(), "Stack`1".into())),
             },
         )]
         .into(),
@@ -207,6 +241,7 @@ fn module_with_fn_type() {
                 parameters: vec![],
                 deprecation: Deprecation::NotDeprecated,
                 documentation: None,
+                external_fsharp: None,
             },
         )]
         .into(),
@@ -238,6 +273,7 @@ fn module_with_tuple_type() {
                 parameters: vec![],
                 deprecation: Deprecation::NotDeprecated,
                 documentation: None,
+                external_fsharp: None,
             },
         )]
         .into(),
@@ -275,6 +311,7 @@ fn module_with_generic_type() {
                     parameters: vec![t1, t2],
                     deprecation: Deprecation::NotDeprecated,
                     documentation: None,
+                    external_fsharp: None,
                 },
             )]
             .into(),
@@ -312,6 +349,7 @@ fn module_with_type_links() {
                     parameters: vec![],
                     deprecation: Deprecation::NotDeprecated,
                     documentation: None,
+                    external_fsharp: None,
                 },
             )]
             .into(),
@@ -349,6 +387,7 @@ fn module_with_type_constructor_documentation() {
                     parameters: vec![],
                     deprecation: Deprecation::NotDeprecated,
                     documentation: Some("type documentation".into()),
+                    external_fsharp: None,
                 },
             )]
             .into(),
@@ -389,6 +428,7 @@ fn module_with_type_constructor_origin() {
                     parameters: vec![],
                     deprecation: Deprecation::NotDeprecated,
                     documentation: None,
+                    external_fsharp: None,
                 },
             )]
             .into(),
@@ -1253,6 +1293,7 @@ fn deprecated_type() {
                     message: "oh no".into(),
                 },
                 documentation: None,
+                external_fsharp: None,
             },
         )]
         .into(),
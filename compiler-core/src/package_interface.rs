@@ -413,6 +413,7 @@ impl ModuleInterface {
                     location: _,
                     name_location: _,
                     end_position: _,
+                    external_fsharp: _,
                 }) => {
                     let mut id_map = IdMap::new();
 
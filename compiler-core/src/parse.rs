@@ -2217,6 +2217,7 @@ where
             constructors,
             typed_parameters: vec![],
             deprecation: std::mem::take(&mut attributes.deprecated),
+            external_fsharp: attributes.external_fsharp.take(),
         })))
     }
 
@@ -3302,6 +3303,7 @@ functions are declared separately from types.";
             Token::Name { name } => match name.as_str() {
                 "javascript" => Ok(Target::JavaScript),
                 "erlang" => Ok(Target::Erlang),
+                "fsharp" => Ok(Target::FSharp),
                 "js" => {
                     self.warnings
                         .push(DeprecatedSyntaxWarning::DeprecatedTargetShorthand {
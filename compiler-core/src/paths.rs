@@ -82,6 +82,14 @@ impl ProjectPaths {
         self.build_directory().join("erlang-shipment")
     }
 
+    pub fn nuget_package_directory(&self) -> Utf8PathBuf {
+        self.build_directory().join("nuget")
+    }
+
+    pub fn native_binary_directory(&self) -> Utf8PathBuf {
+        self.build_directory().join("native")
+    }
+
     pub fn build_documentation_directory(&self, package: &str) -> Utf8PathBuf {
         self.build_directory_for_mode(Mode::Dev)
             .join("docs")
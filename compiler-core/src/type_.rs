@@ -537,6 +537,7 @@ impl ValueConstructorVariant {
             Self::Record {
                 name,
                 arity,
+                variants_count,
                 field_map,
                 location,
                 documentation,
@@ -545,6 +546,7 @@ impl ValueConstructorVariant {
                 name: name.clone(),
                 field_map: field_map.clone(),
                 arity: *arity,
+                variants_count: *variants_count,
                 type_,
                 location: *location,
                 documentation: documentation.clone(),
@@ -671,6 +673,7 @@ pub enum ModuleValueConstructor {
     Record {
         name: EcoString,
         arity: u16,
+        variants_count: u16,
         type_: Arc<Type>,
         field_map: Option<FieldMap>,
         location: SrcSpan,
@@ -887,6 +890,7 @@ pub struct PatternConstructor {
     pub module: EcoString,
     pub location: SrcSpan,
     pub constructor_index: u16,
+    pub variants_count: u16,
 }
 
 impl PatternConstructor {
@@ -1073,6 +1077,9 @@ pub struct TypeConstructor {
     pub type_: Arc<Type>,
     pub deprecation: Deprecation,
     pub documentation: Option<EcoString>,
+    /// The .NET namespace and type name of an external type, given with an
+    /// `@external(fsharp, ...)` attribute.
+    pub external_fsharp: Option<(EcoString, EcoString)>,
 }
 impl TypeConstructor {
     pub(crate) fn with_location(mut self, location: SrcSpan) -> Self {
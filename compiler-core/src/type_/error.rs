@@ -435,6 +435,63 @@ pub enum Error {
         name: EcoString,
     },
 
+    /// A type has been given an F# implementation with an
+    /// `@external(fsharp, ...)` attribute but it also has constructors.
+    ExternalTypeWithConstructors {
+        location: SrcSpan,
+        name: EcoString,
+    },
+
+    /// A type's F# implementation has been given but it does not have a valid
+    /// namespace.
+    InvalidExternalFSharpNamespace {
+        location: SrcSpan,
+        namespace: EcoString,
+        name: EcoString,
+    },
+
+    /// A type's F# implementation has been given but it does not have a valid
+    /// type name.
+    InvalidExternalFSharpType {
+        location: SrcSpan,
+        type_name: EcoString,
+        name: EcoString,
+    },
+
+    /// A type's F# implementation has been given with a generic arity that
+    /// does not match the number of parameters of the type.
+    ExternalFSharpTypeArity {
+        location: SrcSpan,
+        name: EcoString,
+        expected: usize,
+        given: usize,
+    },
+
+    /// A function's F# implementation has been given but its function name
+    /// is not in the form of any of the supported calling conventions.
+    InvalidExternalFSharpFunction {
+        location: SrcSpan,
+        function: EcoString,
+        name: EcoString,
+    },
+
+    /// A function's F# implementation is a constructor, but its module is
+    /// the path of an F# file rather than the type to construct.
+    ExternalFSharpConstructorFile {
+        location: SrcSpan,
+        module: EcoString,
+        name: EcoString,
+    },
+
+    /// A function's F# implementation is a method or property of its first
+    /// argument, but the function does not take the arguments it needs.
+    ExternalFSharpMemberArity {
+        location: SrcSpan,
+        function: EcoString,
+        name: EcoString,
+        arity: usize,
+    },
+
     /// A case expression is missing one or more patterns to match all possible
     /// values of the type.
     InexhaustiveCaseExpression {
@@ -960,6 +1017,13 @@ impl Error {
             | Error::UnsupportedExpressionTarget { location, .. }
             | Error::InvalidExternalJavascriptModule { location, .. }
             | Error::InvalidExternalJavascriptFunction { location, .. }
+            | Error::ExternalTypeWithConstructors { location, .. }
+            | Error::InvalidExternalFSharpNamespace { location, .. }
+            | Error::InvalidExternalFSharpType { location, .. }
+            | Error::ExternalFSharpTypeArity { location, .. }
+            | Error::InvalidExternalFSharpFunction { location, .. }
+            | Error::ExternalFSharpConstructorFile { location, .. }
+            | Error::ExternalFSharpMemberArity { location, .. }
             | Error::InexhaustiveCaseExpression { location, .. }
             | Error::InexhaustiveLetAssignment { location, .. }
             | Error::UnusedTypeAliasParameter { location, .. }
@@ -2911,10 +2911,13 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
 
                 let constructor = self.infer_value_constructor(&module, &name, &location)?;
 
-                let (tag, field_map) = match &constructor.variant {
+                let (tag, field_map, variants_count) = match &constructor.variant {
                     ValueConstructorVariant::Record {
-                        name, field_map, ..
-                    } => (name.clone(), field_map.clone()),
+                        name,
+                        field_map,
+                        variants_count,
+                        ..
+                    } => (name.clone(), field_map.clone(), *variants_count),
 
                     ValueConstructorVariant::ModuleFn { .. }
                     | ValueConstructorVariant::LocalVariable { .. } => {
@@ -2949,6 +2952,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                             name: name.clone(),
                             field_map: field_map.clone(),
                             arity: args.len() as u16,
+                            variants_count,
                             type_: Arc::clone(&type_),
                             location: constructor.variant.definition_location(),
                             documentation: None,
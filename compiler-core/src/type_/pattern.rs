@@ -700,6 +700,7 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                         module,
                         location,
                         variant_index: constructor_index,
+                        variants_count,
                         ..
                     } => PatternConstructor {
                         documentation: documentation.clone(),
@@ -708,6 +709,7 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                         module: module.clone(),
                         location: *location,
                         constructor_index: *constructor_index,
+                        variants_count: *variants_count,
                     },
                     ValueConstructorVariant::LocalVariable { .. }
                     | ValueConstructorVariant::LocalConstant { .. }
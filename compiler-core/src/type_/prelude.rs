@@ -240,6 +240,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> ModuleInterface {
                     publicity: Publicity::Public,
                     deprecation: NotDeprecated,
                     documentation: None,
+                    external_fsharp: None,
                 };
                 let _ = prelude.types.insert(BIT_ARRAY.into(), v.clone());
             }
@@ -303,6 +304,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> ModuleInterface {
                         publicity: Publicity::Public,
                         deprecation: NotDeprecated,
                         documentation: None,
+                        external_fsharp: None,
                     },
                 );
             }
@@ -318,6 +320,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> ModuleInterface {
                         publicity: Publicity::Public,
                         deprecation: NotDeprecated,
                         documentation: None,
+                        external_fsharp: None,
                     },
                 );
             }
@@ -333,6 +336,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> ModuleInterface {
                         publicity: Publicity::Public,
                         deprecation: NotDeprecated,
                         documentation: None,
+                        external_fsharp: None,
                     },
                 );
             }
@@ -349,6 +353,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> ModuleInterface {
                         publicity: Publicity::Public,
                         deprecation: NotDeprecated,
                         documentation: None,
+                        external_fsharp: None,
                     },
                 );
             }
@@ -380,6 +385,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> ModuleInterface {
                         publicity: Publicity::Public,
                         deprecation: NotDeprecated,
                         documentation: None,
+                        external_fsharp: None,
                     },
                 );
                 let _ = prelude.types_value_constructors.insert(
@@ -409,6 +415,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> ModuleInterface {
                         publicity: Publicity::Public,
                         deprecation: NotDeprecated,
                         documentation: None,
+                        external_fsharp: None,
                     },
                 );
                 let _ = prelude.types_value_constructors.insert(
@@ -480,6 +487,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> ModuleInterface {
                         publicity: Publicity::Public,
                         deprecation: NotDeprecated,
                         documentation: None,
+                        external_fsharp: None,
                     },
                 );
             }
@@ -495,6 +503,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> ModuleInterface {
                         publicity: Publicity::Public,
                         deprecation: NotDeprecated,
                         documentation: None,
+                        external_fsharp: None,
                     },
                 );
             }
@@ -12,6 +12,18 @@ pub const x = 1
     );
 }
 
+#[test]
+fn excluded_fsharp_error() {
+    assert_module_infer!(
+        "@target(fsharp)
+pub type X = Y
+
+pub const x = 1
+",
+        vec![("x", "Int")],
+    );
+}
+
 #[test]
 fn alias() {
     assert_module_infer!(
@@ -234,3 +234,109 @@ pub const wobble = wobble",
         vec![("wobble", "fn() -> Int")],
     );
 }
+
+#[test]
+fn fsharp_external_type() {
+    assert_module_infer!(
+        r#"@external(fsharp, "System.Collections.Generic", "Stack`1")
+pub type Stack(a)
+
+pub fn identity(stack: Stack(a)) -> Stack(a) {
+  stack
+}
+"#,
+        vec![("identity", "fn(Stack(a)) -> Stack(a)")]
+    );
+}
+
+#[test]
+fn fsharp_external_type_with_constructors() {
+    assert_module_error!(
+        r#"@external(fsharp, "System", "Version")
+pub type Version {
+  Version(major: Int)
+}
+"#
+    );
+}
+
+#[test]
+fn fsharp_external_type_invalid_namespace() {
+    assert_module_error!(
+        r#"@external(fsharp, "System..Collections", "Stack")
+pub type Stack(a)
+"#
+    );
+}
+
+#[test]
+fn fsharp_external_type_invalid_type_name() {
+    assert_module_error!(
+        r#"@external(fsharp, "System.Collections.Generic", "Stack<'a>")
+pub type Stack(a)
+"#
+    );
+}
+
+#[test]
+fn fsharp_external_type_wrong_arity() {
+    assert_module_error!(
+        r#"@external(fsharp, "System.Collections.Generic", "Dictionary`2")
+pub type Dictionary(a)
+"#
+    );
+}
+
+#[test]
+fn fsharp_external_function_invalid_name() {
+    assert_module_error!(
+        r#"@external(fsharp, "System.Math", "Max(a, b)")
+fn max(a: Int, b: Int) -> Int
+"#
+    );
+}
+
+#[test]
+fn fsharp_external_function_operator_name() {
+    assert_module_error!(
+        r#"@external(fsharp, "Operators", "(+)")
+fn add(a: Int, b: Int) -> Int
+"#
+    );
+}
+
+#[test]
+fn fsharp_external_function_invalid_member_name() {
+    assert_module_error!(
+        r#"@external(fsharp, "System.String", ".Trim.Start()")
+fn trim_start(string: String) -> String
+"#
+    );
+}
+
+#[test]
+fn fsharp_external_instance_method_without_receiver() {
+    assert_module_error!(
+        r#"@external(fsharp, "System.Guid", ".ToString()")
+fn to_string() -> String
+"#
+    );
+}
+
+#[test]
+fn fsharp_external_property_with_arguments() {
+    assert_module_error!(
+        r#"@external(fsharp, "System.String", ".Length")
+fn length(string: String, other: String) -> Int
+"#
+    );
+}
+
+#[test]
+fn fsharp_external_constructor_from_file() {
+    assert_module_error!(
+        r#"@external(fsharp, "./ffi.fs", "new")
+fn new(a: Int, b: Int) -> Int
+"#
+    );
+}
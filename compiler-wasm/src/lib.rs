@@ -117,8 +117,10 @@ pub fn compile_package(project_id: usize, target: &str) -> Result<(), String> {
     let target = match target.to_lowercase().as_str() {
         "erl" | "erlang" => Target::Erlang,
         "js" | "javascript" => Target::JavaScript,
+        "fs" | "fsharp" => Target::FSharp,
         _ => {
-            let msg = format!("Unknown target `{target}`, expected `erlang` or `javascript`");
+            let msg =
+                format!("Unknown target `{target}`, expected `erlang`, `javascript` or `fsharp`");
             return Err(msg);
         }
     };
@@ -151,6 +153,17 @@ pub fn read_compiled_erlang(project_id: usize, module_name: &str) -> Option<Stri
     fs.read(&Utf8PathBuf::from(path)).ok()
 }
 
+/// Get the compiled F# output for a given module.
+///
+/// You need to call `compile_package` before calling this function.
+///
+#[wasm_bindgen]
+pub fn read_compiled_fsharp(project_id: usize, module_name: &str) -> Option<String> {
+    let fs = get_filesystem(project_id);
+    let path = format!("/build/_gleam_artefacts/src/{module_name}.fs");
+    fs.read(&Utf8PathBuf::from(path)).ok()
+}
+
 /// Clear any stored warnings. This is performed automatically when before compilation.
 ///
 #[wasm_bindgen]
@@ -184,7 +197,10 @@ fn do_compile_package(project: Project, target: Target) -> Result<(), Error> {
             emit_typescript_definitions: false,
             prelude_location: Utf8PathBuf::from("./gleam_prelude.mjs"),
         },
-        Target::FSharp => TargetCodegenConfiguration::FSharp,
+        // There is no .NET SDK in the browser so only the F# is generated
+        Target::FSharp => TargetCodegenConfiguration::FSharp {
+            dotnet_build: false,
+        },
     };
 
     tracing::info!("Compiling package");
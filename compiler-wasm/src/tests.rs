@@ -102,3 +102,19 @@ fn test_warnings() {
     assert!(pop_warning(0).is_some());
     assert!(pop_warning(0).is_none());
 }
+
+#[wasm_bindgen_test]
+fn test_compile_package_fsharp() {
+    reset_filesystem(0);
+    write_module(0, "one/two", "pub const x = 1");
+    write_module(0, "up/down", "import one/two pub fn go() { two.x }");
+    assert!(compile_package(0, "fsharp").is_ok());
+
+    assert!(read_compiled_fsharp(0, "one/two")
+        .expect("one/two should be compiled")
+        .contains("x"));
+    assert!(read_compiled_fsharp(0, "up/down")
+        .expect("up/down should be compiled")
+        .contains("go"));
+    assert_eq!(read_compiled_fsharp(0, "not/a/module"), None);
+}
@@ -33,7 +33,9 @@ pub fn prepare(path: &str) -> String {
             emit_typescript_definitions: config.javascript.typescript_declarations,
             prelude_location: Utf8PathBuf::from("../prelude.mjs"),
         },
-        Target::FSharp => TargetCodegenConfiguration::FSharp,
+        Target::FSharp => TargetCodegenConfiguration::FSharp {
+            dotnet_build: false,
+        },
     };
 
     let ids = gleam_core::uid::UniqueIdGenerator::new();
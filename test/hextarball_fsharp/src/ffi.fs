@@ -0,0 +1,3 @@
+module Ffi
+
+let shout (text: string) = text.ToUpperInvariant()
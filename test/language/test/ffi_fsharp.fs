@@ -0,0 +1,17 @@
+module FfiFSharp
+
+let append (a: string) (b: string) = a + b
+
+let print (s: string) : unit = System.Console.Write s
+
+let to_string (a: 'a) =
+    match box a with
+    | :? int64 as i -> string i
+    | :? float as f -> string f
+    | _ -> sprintf "%A" a
+
+let file_exists (path: string) = System.IO.File.Exists path
+
+let halt (code: int64) : unit = exit (int code)
+
+let to_dynamic (a: 'a) : obj = box a